@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use hdk::prelude::*;
 use std::convert::Infallible;
 
@@ -19,6 +20,14 @@ pub enum SocialContextError {
     InternalError(&'static str),
     #[error("Invalid Request Data. Error: {0}")]
     RequestError(&'static str),
+    #[error("Permission Denied. Error: {0}")]
+    PermissionDenied(String),
+    #[error("Spam limit of {0} links every {1} seconds reached")]
+    SpamLimit(usize, i64),
+    #[error("LinkExpression timestamp {0} is more than {1} seconds away from the current time")]
+    InvalidTimestamp(DateTime<Utc>, i64),
+    #[error("Signals are disabled by the enable_signals DNA property")]
+    SignalsDisabled,
     #[error(transparent)]
     IndexError(#[from] IndexError),
 }
@@ -1,8 +1,15 @@
 use chrono::{DateTime, Utc};
 use hc_time_index::IndexableEntry;
 use hdk::prelude::*;
+use k256::ecdsa::signature::Verifier;
 
-use crate::{AgentReference, LinkExpression};
+use crate::errors::{SocialContextError, SocialContextResult};
+use crate::inputs::{ExpressionProof, IndexMode, LinkCursor, LinkExpressionProofData, Order, SyncCursor, SyncPosition, Triple};
+use crate::utils::{
+    ad4m_proof_messages, decode_secp256k1_signature, decode_signature, get_language, link_sort_key, proof_key_of,
+    resolve_proof_key, rules_permit, ProofKey, SyncChange,
+};
+use crate::{AgentReference, LinkExpression, LinkRemoval, Permissions, ENABLE_TIME_INDEX};
 
 impl IndexableEntry for LinkExpression {
     fn entry_time(&self) -> DateTime<Utc> {
@@ -24,8 +31,186 @@ impl IndexableEntry for AgentReference {
     }
 }
 
+impl IndexableEntry for LinkRemoval {
+    fn entry_time(&self) -> DateTime<Utc> {
+        self.timestamp.to_owned()
+    }
+
+    fn hash(&self) -> ExternResult<EntryHash> {
+        hash_entry(self)
+    }
+}
+
 impl LinkExpression {
     pub fn get_sb(self) -> ExternResult<SerializedBytes> {
         Ok(self.try_into()?)
     }
+
+    pub fn proof_data(&self) -> LinkExpressionProofData {
+        LinkExpressionProofData {
+            author: self.author.clone(),
+            data: self.data.clone(),
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Resolve the key that must have signed this LinkExpression from its author DID.
+    /// The proof key has to reference the author, so a proof made with any other key is rejected before its signature is checked
+    pub(crate) fn signing_key(&self) -> SocialContextResult<ProofKey> {
+        if !proof_key_of(&self.author, &self.proof.key) {
+            return Err(SocialContextError::RequestError("LinkExpression proof key does not belong to its author"));
+        };
+        resolve_proof_key(&self.author)
+            .ok_or(SocialContextError::RequestError("Could not resolve LinkExpression author to an ed25519 or secp256k1 did:key"))
+    }
+
+    /// Check if this committed LinkExpression is the one a zome caller submitted.
+    /// Fields that add_link fills in only have to match when the caller supplied them
+    pub fn is_committed_form_of(&self, submitted: &LinkExpression) -> bool {
+        self.author == submitted.author
+            && self.data == submitted.data
+            && self.timestamp == submitted.timestamp
+            && self.proof == submitted.proof
+            && submitted.permissions.as_ref().map_or(true, |permissions| self.permissions.as_ref() == Some(permissions))
+            && submitted.did_binding.as_ref().map_or(true, |did_binding| self.did_binding.as_ref() == Some(did_binding))
+            && submitted.index_mode.as_ref().map_or(true, |index_mode| self.index_mode.as_ref() == Some(index_mode))
+    }
+
+    /// Check that the proof signature was made over this LinkExpression by the author's key.
+    /// ed25519 keys sign the canonical author, data & timestamp; secp256k1 keys sign the data & timestamp the way AD4M agents do
+    pub fn verify_proof(&self) -> SocialContextResult<bool> {
+        match self.signing_key()? {
+            ProofKey::Ed25519(key) => {
+                let signature = self.proof.signature()?;
+                Ok(verify_signature(key, signature, self.proof_data())?)
+            }
+            ProofKey::Secp256k1(key) => {
+                let signature = decode_secp256k1_signature(&self.proof.signature).map_err(SocialContextError::RequestError)?;
+                Ok(ad4m_proof_messages(self)
+                    .iter()
+                    .any(|message| key.verify(message, &signature).is_ok()))
+            }
+        }
+    }
+}
+
+impl ExpressionProof {
+    /// Decode the proof signature, expected to be a hex encoded 64 byte signature
+    pub fn signature(&self) -> SocialContextResult<Signature> {
+        decode_signature(&self.signature).map_err(SocialContextError::RequestError)
+    }
+}
+
+impl IndexMode {
+    /// Use the supplied index mode or fall back to the enable_time_index DNA property
+    pub fn or_default(index_mode: Option<IndexMode>) -> IndexMode {
+        index_mode.unwrap_or_else(|| {
+            if *ENABLE_TIME_INDEX {
+                IndexMode::TimeIndex
+            } else {
+                IndexMode::Path
+            }
+        })
+    }
+}
+
+impl LinkCursor {
+    pub fn for_link(link: &LinkExpression) -> ExternResult<LinkCursor> {
+        Ok(LinkCursor {
+            timestamp: link.timestamp,
+            author: link.author.clone(),
+            data: link.data.clone(),
+            hash: link.hash()?,
+        })
+    }
+
+    /// Count the links at the start of a page, sorted in the given order, that were already returned up to this cursor.
+    /// These are the links up to & including the cursor link or, if it has been removed since, the links that sort at or before it
+    pub fn skip_count(&self, links: &[LinkExpression], order: Order) -> ExternResult<usize> {
+        for (position, link) in links.iter().enumerate() {
+            if link.hash()? == self.hash {
+                return Ok(position + 1);
+            };
+        }
+        let cursor_key = (self.timestamp, self.author.as_str(), &self.data.source, &self.data.target, &self.data.predicate);
+        Ok(links
+            .iter()
+            .take_while(|link| match order {
+                Order::Ascending => link_sort_key(link) <= cursor_key,
+                Order::Descending => link_sort_key(link) >= cursor_key,
+            })
+            .count())
+    }
+
+    pub fn encode(self) -> SocialContextResult<String> {
+        let bytes = SerializedBytes::try_from(self)?;
+        Ok(hex::encode(bytes.bytes()))
+    }
+
+    pub fn decode(cursor: &str) -> SocialContextResult<LinkCursor> {
+        let bytes = hex::decode(cursor)
+            .map_err(|_| SocialContextError::RequestError("Could not decode get_links cursor"))?;
+        Ok(LinkCursor::try_from(SerializedBytes::from(UnsafeBytes::from(bytes)))?)
+    }
+}
+
+impl SyncCursor {
+    /// Cursor continuing after the given change
+    pub(crate) fn after(change: &SyncChange) -> SyncCursor {
+        SyncCursor {
+            timestamp: change.timestamp,
+            last_change: Some(SyncPosition {
+                removed: change.removed,
+                author: change.link.author.clone(),
+                data: change.link.data.clone(),
+            }),
+        }
+    }
+
+    /// Check if a change sorts after the last change returned up to this cursor; always true once all changes were returned
+    pub(crate) fn is_before(&self, change: &SyncChange) -> bool {
+        match &self.last_change {
+            Some(position) => {
+                change.key() > (self.timestamp, position.removed, position.author.as_str(), &position.data.source, &position.data.target, &position.data.predicate)
+            }
+            None => true,
+        }
+    }
+
+    pub fn encode(self) -> SocialContextResult<String> {
+        let bytes = SerializedBytes::try_from(self)?;
+        Ok(hex::encode(bytes.bytes()))
+    }
+
+    pub fn decode(cursor: &str) -> SocialContextResult<SyncCursor> {
+        let bytes = hex::decode(cursor)
+            .map_err(|_| SocialContextError::RequestError("Could not decode get_links_since cursor"))?;
+        Ok(SyncCursor::try_from(SerializedBytes::from(UnsafeBytes::from(bytes)))?)
+    }
+}
+
+impl Permissions {
+    /// Check a triple against the predicate & language rules.
+    /// Sources & targets without a language are not subject to the language rules
+    pub fn check_triple(&self, triple: &Triple) -> SocialContextResult<()> {
+        if let Some(predicate) = &triple.predicate {
+            if !rules_permit(&self.predicates, predicate) {
+                return Err(SocialContextError::PermissionDenied(format!(
+                    "Predicate {} is not permitted in this social context",
+                    predicate
+                )));
+            };
+        };
+        for uri in triple.source.iter().chain(triple.target.iter()) {
+            if let Some(language) = get_language(uri) {
+                if !rules_permit(&self.languages, language) {
+                    return Err(SocialContextError::PermissionDenied(format!(
+                        "Language {} of {} is not permitted in this social context",
+                        language, uri
+                    )));
+                };
+            };
+        }
+        Ok(())
+    }
 }
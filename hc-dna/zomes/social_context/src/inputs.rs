@@ -2,19 +2,28 @@ use chrono::{DateTime, Utc};
 use hdk::prelude::*;
 use crate::LinkExpression;
 
-#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug, PartialEq)]
 pub struct ExpressionProof {
     pub signature: String,
     pub key: String,
 }
 
-#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug, PartialEq)]
 pub struct Triple {
     pub source: Option<String>,
     pub target: Option<String>,
     pub predicate: Option<String>,
 }
 
+/// The canonical form of a LinkExpression that is covered by the ExpressionProof signature of an ed25519 did:key author.
+/// Clients should sign the msgpack serialization of this struct (fields in this order) with the key referenced in ExpressionProof.key
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct LinkExpressionProofData {
+    pub author: String,
+    pub data: Triple,
+    pub timestamp: DateTime<Utc>,
+}
+
 #[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
 pub struct GetLinks {
     #[serde(flatten)]
@@ -23,7 +32,65 @@ pub struct GetLinks {
     pub from_date: Option<DateTime<Utc>>,
     #[serde(rename(serialize = "untilDate", deserialize = "untilDate"))]
     pub until_date: Option<DateTime<Utc>>,
-    pub limit: Option<usize>
+    pub limit: Option<usize>,
+    #[serde(rename(serialize = "indexMode", deserialize = "indexMode"))]
+    pub index_mode: Option<IndexMode>,
+    /// Cursor returned in a previous GetLinksResponse; results continue after the last link of that page
+    pub cursor: Option<String>,
+    /// Order of results by timestamp; when not supplied the order follows the arrangement of fromDate & untilDate
+    pub order: Option<Order>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum Order {
+    Ascending,
+    Descending,
+}
+
+/// Position of the last LinkExpression in a page of get_links results, handed to zome callers as an opaque string.
+/// Holds the sort key of the link so the position is still known if the link is removed before the next page is loaded
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct LinkCursor {
+    pub timestamp: DateTime<Utc>,
+    pub author: String,
+    pub data: Triple,
+    pub hash: EntryHash,
+}
+
+/// Either the moment to start syncing from, or the cursor returned by a previous get_links_since call
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct GetLinksSince {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub cursor: Option<String>,
+    /// Max number of additions & removals together to return
+    pub limit: Option<usize>,
+}
+
+/// How far a get_links_since caller has been synced, handed to zome callers as an opaque string.
+/// When the previous call was limited, last_change is the last change it returned and timestamp is the time of that change;
+/// otherwise all changes were returned up until timestamp, the time of that call
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct SyncCursor {
+    pub timestamp: DateTime<Utc>,
+    pub last_change: Option<SyncPosition>,
+}
+
+/// Sort key of a get_links_since change besides its time
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct SyncPosition {
+    pub removed: bool,
+    pub author: String,
+    pub data: Triple,
+}
+
+/// Allow & deny lists for values in a Permissions entry.
+/// When allow is supplied only listed values are permitted; a denied value is never permitted
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug, Default)]
+pub struct AccessRules {
+    pub allow: Option<Vec<String>>,
+    #[serde(default)]
+    pub deny: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -34,11 +101,21 @@ pub enum IndexStrategy {
     Simple,
 }
 
+/// The index a LinkExpression is written to and read from.
+/// When not supplied in a zome call the enable_time_index DNA property decides which index is used
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum IndexMode {
+    TimeIndex,
+    Path,
+}
+
 #[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AddLinkInput {
     pub link_expression: LinkExpression,
     pub index_strategy: IndexStrategy,
+    pub index_mode: Option<IndexMode>,
 }
 
 #[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
@@ -47,4 +124,67 @@ pub struct UpdateLinkInput {
     pub source: LinkExpression,
     pub target: LinkExpression,
     pub index_strategy: IndexStrategy,
+    pub index_mode: Option<IndexMode>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum Availability {
+    Online,
+    Away,
+    Busy,
+}
+
+/// Small presence payload published by an agent alongside their AgentReference
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceStatus {
+    pub availability: Availability,
+    /// Custom status text
+    pub message: Option<String>,
+    /// Perspective the agent is currently viewing
+    pub perspective: Option<String>,
+}
+
+/// Interest in link signals; each supplied element must match the link for the pattern to match
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TriplePattern {
+    pub source: Option<String>,
+    pub predicate: Option<String>,
+    pub target_prefix: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug, Default)]
+pub struct AddActiveAgentLinkInput {
+    pub status: Option<PresenceStatus>,
+    /// Only receive link signals matching one of these patterns; when empty all link signals are received
+    #[serde(default)]
+    pub subscriptions: Vec<TriplePattern>,
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct EphemeralSignalInput {
+    /// Application defined type of the event, e.g. "typing"
+    pub kind: String,
+    /// Application defined (e.g JSON encoded) event data
+    pub payload: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct AddDidBindingInput {
+    pub did: String,
+    /// Hex encoded signature of the raw 39 byte agent key of the calling agent, made with the key of the did:key DID
+    pub signature: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct DirectSignalInput {
+    #[serde(default)]
+    pub agents: Vec<AgentPubKey>,
+    /// DIDs resolved to agents through their DidBinding(s)
+    #[serde(default)]
+    pub dids: Vec<String>,
+    pub kind: String,
+    pub payload: String,
 }
@@ -1,19 +1,29 @@
 use chrono::{DateTime, Utc};
 use hdk::prelude::*;
+use holo_hash::AgentPubKeyB64;
 use lazy_static::lazy_static;
 
 mod errors;
 mod impls;
 mod inputs;
 mod methods;
+mod signals;
 mod utils;
 mod validation;
 
 use inputs::*;
+use signals::*;
 pub use validation::*;
 
 
-entry_defs![PathEntry::entry_def(), LinkExpression::entry_def(), AgentReference::entry_def()];
+entry_defs![
+    PathEntry::entry_def(),
+    LinkExpression::entry_def(),
+    AgentReference::entry_def(),
+    DidBinding::entry_def(),
+    LinkRemoval::entry_def(),
+    Permissions::entry_def()
+];
 
 #[hdk_entry(id = "link_data", visibility = "public")]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +33,18 @@ pub struct LinkExpression {
     pub data: Triple,
     pub timestamp: DateTime<Utc>,
     pub proof: ExpressionProof,
+    //Header of the Permissions version this link was created under; add_link fills in the latest version when not supplied.
+    //Callers can leave it out when removing or updating the link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<HeaderHash>,
+    //Header of the DidBinding of the author to the committing agent; add_link fills this in when validate_link_authors is set.
+    //Callers can leave it out when removing or updating the link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub did_binding: Option<HeaderHash>,
+    //Index this link was written to; add_link records it so removal cleans up exactly that index.
+    //Callers can leave it out when removing or updating the link, the committed entry is then looked up by its other fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_mode: Option<IndexMode>,
 }
 
 #[hdk_entry(id = "agent_reference", visbility = "public")]
@@ -30,6 +52,43 @@ pub struct LinkExpression {
 pub struct AgentReference {
     pub agent: AgentPubKey,
     pub timestamp: DateTime<Utc>,
+    pub status: Option<PresenceStatus>,
+    #[serde(default)]
+    pub subscriptions: Vec<TriplePattern>,
+}
+
+/// Claim committed by an agent that binds a DID to their agent public key
+#[hdk_entry(id = "did_binding", visibility = "public")]
+#[derive(Clone)]
+pub struct DidBinding {
+    pub did: String,
+    pub agent: AgentPubKey,
+    //Hex encoded signature of the raw 39 byte agent key, made with the key of the did:key DID to prove the agent controls the DID
+    pub signature: String,
+}
+
+/// Record of a LinkExpression being removed, indexed by time of removal so that offline agents can catch up on removals
+#[hdk_entry(id = "link_removal", visibility = "public")]
+#[derive(Clone)]
+pub struct LinkRemoval {
+    pub link: LinkExpression,
+    //Header of the removed link_data entry, used in validation to check the remover could delete it
+    pub link_header: HeaderHash,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The rules of this social context; only agents listed in the admins DNA property can create or update it.
+/// Every version is an update of the first Permissions entry, which is found through the "permissions" anchor
+#[hdk_entry(id = "permissions", visibility = "public")]
+#[derive(Clone)]
+pub struct Permissions {
+    pub moderators: Vec<AgentPubKey>,
+    //Rules for LinkExpression predicates
+    #[serde(default)]
+    pub predicates: AccessRules,
+    //Rules for the expression language (URI scheme before "://") of LinkExpression sources & targets
+    #[serde(default)]
+    pub languages: AccessRules,
 }
 
 pub struct SocialContextDNA;
@@ -46,12 +105,25 @@ fn init(_: ()) -> ExternResult<InitCallbackResult> {
         access: ().into(),
         functions,
     })?;
+
+    //Links must reference a Permissions version when admins are set, so the first admin to join creates the default (unrestricted) one
+    if ADMINS.contains(&agent_info()?.agent_initial_pubkey) {
+        let latest = SocialContextDNA::get_latest_permissions().map_err(|err| WasmError::Host(err.to_string()))?;
+        if latest.is_none() {
+            SocialContextDNA::update_permissions(Permissions {
+                moderators: vec![],
+                predicates: AccessRules::default(),
+                languages: AccessRules::default(),
+            })
+            .map_err(|err| WasmError::Host(err.to_string()))?;
+        };
+    };
     Ok(InitCallbackResult::Pass)
 }
 
 #[hdk_extern]
 fn recv_remote_signal(signal: SerializedBytes) -> ExternResult<()> {
-    let sig: LinkExpression = LinkExpression::try_from(signal.clone())?;
+    let sig = SignalEnvelope::decode(signal);
     Ok(emit_signal(&sig)?)
 }
 
@@ -60,29 +132,131 @@ pub fn add_link(add_link_input: AddLinkInput) -> ExternResult<()> {
     SocialContextDNA::add_link(add_link_input).map_err(|err| WasmError::Host(err.to_string()))
 }
 
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct AddLinkResult {
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct AddLinksResponse(pub Vec<AddLinkResult>);
+
+/// Add many links in one zome call; results are returned in the same order as the inputs.
+/// Note that entries are committed atomically at the end of the call, so a link failing validation or failing to be indexed will still fail the whole call
+#[hdk_extern]
+pub fn add_links(add_links_input: Vec<AddLinkInput>) -> ExternResult<AddLinksResponse> {
+    let results = SocialContextDNA::add_links(add_links_input).map_err(|err| WasmError::Host(err.to_string()))?;
+    Ok(AddLinksResponse(
+        results
+            .into_iter()
+            .map(|result| AddLinkResult {
+                success: result.is_ok(),
+                error: result.err().map(|err| err.to_string()),
+            })
+            .collect(),
+    ))
+}
+
 #[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
 pub struct AddActiveAgentLinkResponse {
+    //When the agent was already online, the time of the reference keeping them online; call again at existing + active_agent_duration_s
     pub existing: Option<DateTime<Utc>>,
 }
 
 #[hdk_extern]
-pub fn add_active_agent_link(_: ()) -> ExternResult<AddActiveAgentLinkResponse> {
-    let res = SocialContextDNA::add_active_agent_link().map_err(|err| WasmError::Host(err.to_string()))?;
+pub fn add_active_agent_link(input: Option<AddActiveAgentLinkInput>) -> ExternResult<AddActiveAgentLinkResponse> {
+    let res = SocialContextDNA::add_active_agent_link(input.unwrap_or_default()).map_err(|err| WasmError::Host(err.to_string()))?;
     Ok(AddActiveAgentLinkResponse {
         existing: res
     })
 }
 
+#[hdk_extern]
+pub fn remove_active_agent_link(_: ()) -> ExternResult<()> {
+    SocialContextDNA::remove_active_agent_link().map_err(|err| WasmError::Host(err.to_string()))
+}
+
+#[hdk_extern]
+pub fn send_ephemeral_signal(input: EphemeralSignalInput) -> ExternResult<()> {
+    SocialContextDNA::send_ephemeral_signal(input).map_err(|err| WasmError::Host(err.to_string()))
+}
+
 #[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
-pub struct GetLinksResponse(pub Vec<LinkExpression>);
+pub struct SendDirectSignalResponse(pub Vec<AgentPubKey>);
 
 #[hdk_extern]
-pub fn get_links(input: GetLinks) -> ExternResult<GetLinksResponse> {
-    Ok(GetLinksResponse(
-        SocialContextDNA::get_links(input).map_err(|err| WasmError::Host(err.to_string()))?,
+pub fn send_direct_signal(input: DirectSignalInput) -> ExternResult<SendDirectSignalResponse> {
+    Ok(SendDirectSignalResponse(
+        SocialContextDNA::send_direct_signal(input).map_err(|err| WasmError::Host(err.to_string()))?,
     ))
 }
 
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveAgent {
+    pub agent: AgentPubKey,
+    pub last_seen: DateTime<Utc>,
+    pub status: Option<PresenceStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct GetActiveAgentsResponse(pub Vec<ActiveAgent>);
+
+#[hdk_extern]
+pub fn get_active_agents(since: Option<DateTime<Utc>>) -> ExternResult<GetActiveAgentsResponse> {
+    Ok(GetActiveAgentsResponse(
+        SocialContextDNA::get_active_agents(since).map_err(|err| WasmError::Host(err.to_string()))?,
+    ))
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct GetLinksResponse {
+    pub links: Vec<LinkExpression>,
+    //Present when the page was filled up to the requested limit; pass back in GetLinks.cursor to get the next page
+    pub cursor: Option<String>,
+}
+
+#[hdk_extern]
+pub fn get_links(input: GetLinks) -> ExternResult<GetLinksResponse> {
+    SocialContextDNA::get_links(input).map_err(|err| WasmError::Host(err.to_string()))
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLinksSinceResponse {
+    pub added: Vec<LinkExpression>,
+    pub removed: Vec<LinkExpression>,
+    //Pass back in GetLinksSince.cursor to get the next page, or on the next sync to get changes made after this call
+    pub cursor: String,
+    //True when the limit was reached; call again with the cursor straight away to get the rest of the changes
+    pub has_more: bool,
+}
+
+/// Get the LinkExpression(s) added & removed since a given moment, whatever IndexMode & IndexStrategy they were added with.
+/// Changes are returned in order of the time they were made, up to the given limit.
+/// Once all changes were returned, syncing from the cursor rescans the sync_overlap_s before it, to catch links that reached this agent late through gossip;
+/// changes in the overlap are returned again so callers should dedupe by entry hash
+#[hdk_extern]
+pub fn get_links_since(input: GetLinksSince) -> ExternResult<GetLinksSinceResponse> {
+    SocialContextDNA::get_links_since(input).map_err(|err| WasmError::Host(err.to_string()))
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkExistsResponse {
+    pub exists: bool,
+    pub link_hashes: Vec<EntryHash>,
+}
+
+#[hdk_extern]
+pub fn link_exists(triple: Triple) -> ExternResult<LinkExistsResponse> {
+    let link_hashes = SocialContextDNA::link_exists(triple).map_err(|err| WasmError::Host(err.to_string()))?;
+    Ok(LinkExistsResponse {
+        exists: !link_hashes.is_empty(),
+        link_hashes,
+    })
+}
+
 #[hdk_extern]
 pub fn update_link(update_link_input: UpdateLinkInput) -> ExternResult<()> {
     SocialContextDNA::update_link(update_link_input).map_err(|err| WasmError::Host(err.to_string()))
@@ -93,12 +267,76 @@ pub fn remove_link(remove_link: LinkExpression) -> ExternResult<()> {
     SocialContextDNA::remove_link(remove_link).map_err(|err| WasmError::Host(err.to_string()))
 }
 
+#[hdk_extern]
+pub fn remove_links(remove_links: Vec<LinkExpression>) -> ExternResult<()> {
+    SocialContextDNA::remove_links(remove_links).map_err(|err| WasmError::Host(err.to_string()))
+}
+
+#[hdk_extern]
+pub fn remove_links_by_hash(link_hashes: Vec<EntryHash>) -> ExternResult<()> {
+    SocialContextDNA::remove_links_by_hash(link_hashes).map_err(|err| WasmError::Host(err.to_string()))
+}
+
+#[hdk_extern]
+pub fn add_did_binding(input: AddDidBindingInput) -> ExternResult<HeaderHash> {
+    SocialContextDNA::add_did_binding(input).map_err(|err| WasmError::Host(err.to_string()))
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct GetPermissionsResponse {
+    //Header of the latest Permissions version; None when admins have not created any permissions yet
+    pub hash: Option<HeaderHash>,
+    pub permissions: Option<Permissions>,
+}
+
+#[hdk_extern]
+pub fn get_permissions(_: ()) -> ExternResult<GetPermissionsResponse> {
+    let latest = SocialContextDNA::get_latest_permissions().map_err(|err| WasmError::Host(err.to_string()))?;
+    Ok(match latest {
+        Some((hash, permissions)) => GetPermissionsResponse {
+            hash: Some(hash),
+            permissions: Some(permissions),
+        },
+        None => GetPermissionsResponse {
+            hash: None,
+            permissions: None,
+        },
+    })
+}
+
+/// Commit a new version of the Permissions entry; can only be called by admins
+#[hdk_extern]
+pub fn update_permissions(permissions: Permissions) -> ExternResult<HeaderHash> {
+    SocialContextDNA::update_permissions(permissions).map_err(|err| WasmError::Host(err.to_string()))
+}
+
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct GetDidAgentsResponse(pub Vec<AgentPubKey>);
+
+#[hdk_extern]
+pub fn get_did_agents(did: String) -> ExternResult<GetDidAgentsResponse> {
+    Ok(GetDidAgentsResponse(
+        SocialContextDNA::get_did_agents(did).map_err(|err| WasmError::Host(err.to_string()))?,
+    ))
+}
+
 #[derive(Serialize, Deserialize, Debug, SerializedBytes)]
 pub struct SocialContextProperties {
     pub active_agent_duration_s: i64,
     pub enable_signals: bool,
-    //TODO: lets set this per add_link zome call and not lock in each DNA to enabling or disabling this feature, there are cases where you want both time indexes and regular
+    //Default index used when add_link/get_links calls do not specify an IndexMode
     pub enable_time_index: bool,
+    pub validate_link_proofs: bool,
+    pub validate_link_authors: bool,
+    //Base64 encoded agent keys allowed to create & update the Permissions entry
+    pub admins: Vec<String>,
+    //Max number of links an agent can add in every max_chunk_interval of their source chain
+    pub enforce_spam_limit: usize,
+    pub max_chunk_interval: i64,
+    //Max distance in seconds of a LinkExpression timestamp from the time it is committed; links are not checked when null
+    pub max_timestamp_skew_s: Option<i64>,
+    //How far before a get_links_since cursor changes are looked for again
+    pub sync_overlap_s: i64,
 }
 
 lazy_static! {
@@ -126,4 +364,68 @@ lazy_static! {
             .expect("Could not convert zome dna properties to SocialContextProperties. Please ensure that your dna properties contains a SocialContextProperties field.");
         properties.enable_time_index
     };
+    pub static ref VALIDATE_LINK_PROOFS: bool = {
+        let host_dna_config = dna_info()
+            .expect("Could not get zome configuration")
+            .properties;
+        let properties = SocialContextProperties::try_from(host_dna_config)
+            .expect("Could not convert zome dna properties to SocialContextProperties. Please ensure that your dna properties contains a SocialContextProperties field.");
+        properties.validate_link_proofs
+    };
+    pub static ref VALIDATE_LINK_AUTHORS: bool = {
+        let host_dna_config = dna_info()
+            .expect("Could not get zome configuration")
+            .properties;
+        let properties = SocialContextProperties::try_from(host_dna_config)
+            .expect("Could not convert zome dna properties to SocialContextProperties. Please ensure that your dna properties contains a SocialContextProperties field.");
+        properties.validate_link_authors
+    };
+    pub static ref ADMINS: Vec<AgentPubKey> = {
+        let host_dna_config = dna_info()
+            .expect("Could not get zome configuration")
+            .properties;
+        let properties = SocialContextProperties::try_from(host_dna_config)
+            .expect("Could not convert zome dna properties to SocialContextProperties. Please ensure that your dna properties contains a SocialContextProperties field.");
+        properties
+            .admins
+            .iter()
+            .map(|admin| {
+                AgentPubKeyB64::from_b64_str(admin)
+                    .expect("Could not convert admins dna property to agent public keys")
+                    .into()
+            })
+            .collect()
+    };
+    pub static ref ENFORCE_SPAM_LIMIT: usize = {
+        let host_dna_config = dna_info()
+            .expect("Could not get zome configuration")
+            .properties;
+        let properties = SocialContextProperties::try_from(host_dna_config)
+            .expect("Could not convert zome dna properties to SocialContextProperties. Please ensure that your dna properties contains a SocialContextProperties field.");
+        properties.enforce_spam_limit
+    };
+    pub static ref MAX_CHUNK_INTERVAL: chrono::Duration = {
+        let host_dna_config = dna_info()
+            .expect("Could not get zome configuration")
+            .properties;
+        let properties = SocialContextProperties::try_from(host_dna_config)
+            .expect("Could not convert zome dna properties to SocialContextProperties. Please ensure that your dna properties contains a SocialContextProperties field.");
+        chrono::Duration::milliseconds(properties.max_chunk_interval)
+    };
+    pub static ref MAX_TIMESTAMP_SKEW: Option<chrono::Duration> = {
+        let host_dna_config = dna_info()
+            .expect("Could not get zome configuration")
+            .properties;
+        let properties = SocialContextProperties::try_from(host_dna_config)
+            .expect("Could not convert zome dna properties to SocialContextProperties. Please ensure that your dna properties contains a SocialContextProperties field.");
+        properties.max_timestamp_skew_s.map(chrono::Duration::seconds)
+    };
+    pub static ref SYNC_OVERLAP: chrono::Duration = {
+        let host_dna_config = dna_info()
+            .expect("Could not get zome configuration")
+            .properties;
+        let properties = SocialContextProperties::try_from(host_dna_config)
+            .expect("Could not convert zome dna properties to SocialContextProperties. Please ensure that your dna properties contains a SocialContextProperties field.");
+        chrono::Duration::seconds(properties.sync_overlap_s)
+    };
 }
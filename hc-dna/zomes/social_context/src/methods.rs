@@ -1,94 +1,354 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, Utc};
 use hc_time_index::{IndexableEntry, SearchStrategy};
 use hdk::prelude::*;
+use std::collections::{BTreeMap, HashSet};
 
-use crate::utils::{generate_link_path_permutations, get_link_permutation_by, LinkPermutation, get_wildcard, dedup};
+use crate::utils::{
+    generate_link_path_permutations, get_link_permutation_by, LinkPermutation, get_wildcard, dedup,
+    triple_matches, filter_by_time_span, sort_links, get_now, unix_epoch, resolve_order, arrange_time_span,
+    to_datetime, within_skew, SyncChange,
+};
 use crate::errors::{SocialContextError, SocialContextResult};
+use crate::validation::can_moderate;
 use crate::{
     GetLinks, LinkExpression, SocialContextDNA, ACTIVE_AGENT_DURATION,
-    ENABLE_SIGNALS, ENABLE_TIME_INDEX, IndexStrategy, AgentReference,
-    AddLinkInput, UpdateLinkInput,
+    ENABLE_SIGNALS, IndexStrategy, IndexMode, AgentReference,
+    AddLinkInput, UpdateLinkInput, DidBinding, VALIDATE_LINK_AUTHORS, Triple,
+    GetLinksResponse, LinkCursor, Order, SocialContextSignal, SignalEnvelope, ActiveAgent,
+    EphemeralSignalInput, DirectSignalInput, AddActiveAgentLinkInput, GetLinksSince,
+    GetLinksSinceResponse, SyncCursor, LinkRemoval, Permissions, ADMINS, AddDidBindingInput,
+    ENFORCE_SPAM_LIMIT, MAX_CHUNK_INTERVAL, MAX_TIMESTAMP_SKEW, SYNC_OVERLAP,
 };
 
 impl SocialContextDNA {
     pub fn add_link(input: AddLinkInput) -> SocialContextResult<()> {
-        //Create the LinkExpression entry
-        create_entry(&input.link_expression)?;
+        let link_expression = SocialContextDNA::commit_link(input)?;
+        SocialContextDNA::send_link_signal(SocialContextSignal::Added(vec![link_expression]))
+    }
+
+    /// Commit & index many LinkExpression(s) in one call, sending a single signal for all links that were added.
+    /// Returns the result of each input in the order they were given
+    pub fn add_links(inputs: Vec<AddLinkInput>) -> SocialContextResult<Vec<SocialContextResult<()>>> {
+        let mut added = vec![];
+        let mut results = vec![];
+        for input in inputs {
+            match SocialContextDNA::prepare_link(input) {
+                Ok((link_expression, link_indexes)) => {
+                    //A committed link must also be indexed, so failing to write it fails the whole call and nothing is committed
+                    SocialContextDNA::write_link(&link_expression, link_indexes)?;
+                    added.push(link_expression);
+                    results.push(Ok(()));
+                }
+                Err(err) => results.push(Err(err)),
+            };
+        }
+
+        if !added.is_empty() {
+            SocialContextDNA::send_link_signal(SocialContextSignal::Added(added))?;
+        };
+        Ok(results)
+    }
 
-        //If signals are enabled from the dna properties
+    /// Send a signal to all recently active agents, if signals are enabled from the dna properties
+    fn send_signal(signal: SocialContextSignal) -> SocialContextResult<()> {
         if *ENABLE_SIGNALS {
-            let now = sys_time()?.as_seconds_and_nanos();
-            let now = DateTime::<Utc>::from_utc(
-                NaiveDateTime::from_timestamp(now.0, now.1),
-                Utc,
-            );
-            //Get recent agents (agents which have marked themselves online in time period now -> ACTIVE_AGENT_DURATION as derived from DNA properties)
-            let recent_agents = hc_time_index::get_links_and_load_for_time_span::<AgentReference>(
-                String::from("active_agent"),
-                now - *ACTIVE_AGENT_DURATION,
-                now,
-                Some(LinkTag::new("")),
-                SearchStrategy::Bfs,
-                None,
+            SocialContextDNA::signal_agents(signal, SocialContextDNA::get_recent_agents()?)?;
+        };
+        Ok(())
+    }
+
+    /// Send a link signal to recently active agents, narrowed to the links that match each agent's subscriptions.
+    /// Agents without subscriptions receive every link signal
+    fn send_link_signal(signal: SocialContextSignal) -> SocialContextResult<()> {
+        if *ENABLE_SIGNALS {
+            let now = get_now()?;
+            let mut unfiltered_agents = vec![];
+            for agent_ref in SocialContextDNA::get_latest_agent_references(now - *ACTIVE_AGENT_DURATION, now)? {
+                if agent_ref.subscriptions.is_empty() {
+                    unfiltered_agents.push(agent_ref.agent);
+                } else if let Some(signal) = signal.filter_for(&agent_ref.subscriptions) {
+                    SocialContextDNA::signal_agents(signal, vec![agent_ref.agent])?;
+                };
+            }
+            if !unfiltered_agents.is_empty() {
+                SocialContextDNA::signal_agents(signal, unfiltered_agents)?;
+            };
+        };
+        Ok(())
+    }
+
+    fn signal_agents(signal: SocialContextSignal, agents: Vec<AgentPubKey>) -> SocialContextResult<()> {
+        debug!("Social-Context.signal_agents: Sending signal to agents: {:#?}", agents);
+        remote_signal(SerializedBytes::try_from(SignalEnvelope::new(signal))?, agents)?;
+        Ok(())
+    }
+
+    /// Fan out a payload to all recently active agents without committing anything to the source chain.
+    /// Fails if signals are disabled from the dna properties
+    pub fn send_ephemeral_signal(input: EphemeralSignalInput) -> SocialContextResult<()> {
+        if !*ENABLE_SIGNALS {
+            return Err(SocialContextError::SignalsDisabled);
+        };
+        let signal = SocialContextSignal::Ephemeral {
+            sender: agent_info()?.agent_latest_pubkey,
+            kind: input.kind,
+            payload: input.payload,
+        };
+        SocialContextDNA::signal_agents(signal, SocialContextDNA::get_recent_agents()?)
+    }
+
+    /// Send a payload only to the given agents & agents bound to the given DIDs, limited to those that are currently active.
+    /// Returns the agents the signal was sent to; fails if signals are disabled from the dna properties
+    pub fn send_direct_signal(input: DirectSignalInput) -> SocialContextResult<Vec<AgentPubKey>> {
+        if !*ENABLE_SIGNALS {
+            return Err(SocialContextError::SignalsDisabled);
+        };
+        let mut recipients = input.agents;
+        for did in input.dids {
+            recipients.extend(SocialContextDNA::get_did_agents(did)?);
+        }
+        let recent_agents = SocialContextDNA::get_recent_agents()?;
+        let recipients = dedup(&recipients)
+            .into_iter()
+            .filter(|agent| recent_agents.contains(agent))
+            .collect::<Vec<AgentPubKey>>();
+
+        if !recipients.is_empty() {
+            let signal = SocialContextSignal::Direct {
+                sender: agent_info()?.agent_latest_pubkey,
+                kind: input.kind,
+                payload: input.payload,
+            };
+            SocialContextDNA::signal_agents(signal, recipients.clone())?;
+        };
+        Ok(recipients)
+    }
+
+    /// Create the LinkExpression entry under the latest Permissions version and index it so its discoverable by source, predicate, target queries,
+    /// returning the committed LinkExpression
+    fn commit_link(input: AddLinkInput) -> SocialContextResult<LinkExpression> {
+        let (link_expression, link_indexes) = SocialContextDNA::prepare_link(input)?;
+        SocialContextDNA::write_link(&link_expression, link_indexes)?;
+        Ok(link_expression)
+    }
+
+    /// Run every check that could reject the LinkExpression & fill in the fields recorded with it, without committing anything.
+    /// Returns the LinkExpression to commit along with the indexes to write it to
+    fn prepare_link(mut input: AddLinkInput) -> SocialContextResult<(LinkExpression, Vec<LinkPermutation>)> {
+        //Generate the indexes first so that invalid input fails before anything is committed
+        let link_indexes = SocialContextDNA::get_link_indexes(&input)?;
+
+        //Reference the binding of the author DID to this agent so validation can check it, failing early if there is none
+        if *VALIDATE_LINK_AUTHORS {
+            let binding = SocialContextDNA::get_own_did_binding(&input.link_expression.author)?.ok_or(
+                SocialContextError::RequestError(
+                    "LinkExpression author DID is not bound to this agent, call add_did_binding first",
+                ),
             )?;
-            let recent_agents = recent_agents
-                .into_iter()
-                .map(|val| val.agent)
-                .collect::<Vec<AgentPubKey>>();
-            let recent_agents = dedup(&recent_agents);
-            debug!("Social-Context.add_link: Sending signal to agents: {:#?}", recent_agents);
-            remote_signal(input.link_expression.clone().get_sb()?, recent_agents)?;
+            input.link_expression.did_binding = Some(binding);
+        };
+
+        //Check the timestamp is close to the time of commit, otherwise the link would be rejected in validation
+        if let Some(skew) = *MAX_TIMESTAMP_SKEW {
+            if !within_skew(input.link_expression.timestamp, get_now()?, skew) {
+                return Err(SocialContextError::InvalidTimestamp(input.link_expression.timestamp, skew.num_seconds()));
+            };
+        };
+
+        //Check the spam limit before committing, otherwise the link would be rejected in validation
+        SocialContextDNA::check_spam_limit()?;
+
+        //Links must be created under the latest Permissions version so that validation checks them against the current rules
+        let latest_permissions = SocialContextDNA::get_latest_permissions()?;
+        let latest_hash = latest_permissions.as_ref().map(|(hash, _)| hash.to_owned());
+        match &input.link_expression.permissions {
+            Some(permissions) if Some(permissions) != latest_hash.as_ref() => {
+                return Err(SocialContextError::RequestError(
+                    "LinkExpression permissions is not the latest version, call get_permissions and try again",
+                ))
+            }
+            Some(_) => (),
+            None if latest_hash.is_none() && !ADMINS.is_empty() => {
+                return Err(SocialContextError::RequestError(
+                    "No permissions have been created yet, an admin must join the social context first",
+                ))
+            }
+            None => input.link_expression.permissions = latest_hash,
+        };
+        if let Some((_, permissions)) = latest_permissions {
+            permissions.check_triple(&input.link_expression.data)?;
+        };
+
+        input.link_expression.index_mode = Some(IndexMode::or_default(input.index_mode.clone()));
+        Ok((input.link_expression, link_indexes))
+    }
+
+    /// Create the entry of a prepared LinkExpression and write its indexes
+    fn write_link(link_expression: &LinkExpression, link_indexes: Vec<LinkPermutation>) -> SocialContextResult<()> {
+        create_entry(link_expression)?;
+        SocialContextDNA::index_link(link_expression, link_indexes)
+    }
+
+    /// Fail if the current agent has already added ENFORCE_SPAM_LIMIT links in the last MAX_CHUNK_INTERVAL of their source chain
+    fn check_spam_limit() -> SocialContextResult<()> {
+        let link_def = entry_def_index!(LinkExpression)?;
+        let window_start = get_now()? - *MAX_CHUNK_INTERVAL;
+        let link_type = EntryType::App(AppEntryType::new(link_def, zome_info()?.id, EntryVisibility::Public));
+        let recent_links = query(ChainQueryFilter::new().entry_type(link_type).header_type(HeaderType::Create))?
+            .iter()
+            .filter(|element| to_datetime(element.header().timestamp()) > window_start)
+            .count();
+        if recent_links >= *ENFORCE_SPAM_LIMIT {
+            return Err(SocialContextError::SpamLimit(*ENFORCE_SPAM_LIMIT, MAX_CHUNK_INTERVAL.num_seconds()));
         };
-        //Index the LinkExpression so its discoverable by source, predicate, target queries
-        SocialContextDNA::index_link(input)?;
         Ok(())
     }
 
-    pub fn add_active_agent_link() -> SocialContextResult<Option<DateTime<Utc>>> {
-        let now = sys_time()?.as_seconds_and_nanos();
-        let now = DateTime::<Utc>::from_utc(
-            NaiveDateTime::from_timestamp(now.0, now.1),
-            Utc,
-        );
-        //Get the recent agents so we can check that the current agent is not already 
-        let recent_agents = hc_time_index::get_links_and_load_for_time_span::<AgentReference>(
+    /// Follow the update chain of the Permissions entry from the "permissions" anchor, returning the header hash & content of the latest version
+    pub fn get_latest_permissions() -> SocialContextResult<Option<(HeaderHash, Permissions)>> {
+        let is_admin_permissions = |element: &Element| {
+            ADMINS.contains(element.header().author())
+                && element.entry().to_app_option::<Permissions>().ok().flatten().is_some()
+        };
+
+        //If admins concurrently created more than one Permissions entry, the first one is used; anchor targets which are not Permissions created by an admin are skipped
+        let anchor = Path::from("permissions");
+        let mut anchor_links = hdk::link::get_links(anchor.path_entry_hash()?, Some(LinkTag::new("permissions")))?;
+        anchor_links.sort_by_key(|link| link.timestamp);
+        let mut first = None;
+        for link in anchor_links {
+            if let Some(element) = get(link.target, GetOptions::latest())? {
+                if is_admin_permissions(&element) {
+                    first = Some(element);
+                    break;
+                };
+            };
+        }
+        let mut element = match first {
+            Some(element) => element,
+            None => return Ok(None),
+        };
+
+        //Walk the update chain, taking the most recent update by an admin at each step
+        while let Some(Details::Element(details)) = get_details(element.header_address().to_owned(), GetOptions::latest())? {
+            let latest_update = details
+                .updates
+                .iter()
+                .filter(|update| ADMINS.contains(update.header().author()))
+                .max_by_key(|update| update.header().timestamp());
+            match latest_update {
+                Some(update) => {
+                    element = get(update.header_address().to_owned(), GetOptions::latest())?.ok_or(
+                        SocialContextError::InternalError("Could not get update of permissions entry"),
+                    )?;
+                }
+                None => break,
+            };
+        }
+
+        let permissions = element.entry().to_app_option::<Permissions>()?.ok_or(
+            SocialContextError::InternalError("Expected element to contain Permissions"),
+        )?;
+        Ok(Some((element.header_address().to_owned(), permissions)))
+    }
+
+    /// Create the Permissions entry, or update its latest version if it already exists
+    pub fn update_permissions(permissions: Permissions) -> SocialContextResult<HeaderHash> {
+        if !ADMINS.contains(&agent_info()?.agent_latest_pubkey) {
+            return Err(SocialContextError::RequestError("Only admins can update permissions"));
+        };
+        match SocialContextDNA::get_latest_permissions()? {
+            Some((latest, _)) => Ok(update_entry(latest, &permissions)?),
+            None => {
+                let header_hash = create_entry(&permissions)?;
+                let anchor = Path::from("permissions");
+                anchor.ensure()?;
+                create_link(anchor.path_entry_hash()?, hash_entry(&permissions)?, LinkTag::new("permissions"))?;
+                Ok(header_hash)
+            }
+        }
+    }
+
+    /// Get recent agents (agents which have marked themselves online in time period now -> ACTIVE_AGENT_DURATION as derived from DNA properties)
+    fn get_recent_agents() -> SocialContextResult<Vec<AgentPubKey>> {
+        let now = get_now()?;
+        let recent_agents = SocialContextDNA::get_agent_references(now - *ACTIVE_AGENT_DURATION, now)?
+            .into_iter()
+            .map(|val| val.agent)
+            .collect::<Vec<AgentPubKey>>();
+        Ok(dedup(&recent_agents))
+    }
+
+    /// Get each agent that has marked themselves online since the given time (or in the last ACTIVE_AGENT_DURATION) along with when they were last seen
+    pub fn get_active_agents(since: Option<DateTime<Utc>>) -> SocialContextResult<Vec<ActiveAgent>> {
+        let now = get_now()?;
+        let since = since.unwrap_or(now - *ACTIVE_AGENT_DURATION);
+        let mut active_agents = SocialContextDNA::get_latest_agent_references(since, now)?
+            .into_iter()
+            .map(|agent_ref| ActiveAgent {
+                agent: agent_ref.agent,
+                last_seen: agent_ref.timestamp,
+                status: agent_ref.status,
+            })
+            .collect::<Vec<ActiveAgent>>();
+        //Most recently seen agents first
+        active_agents.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+        Ok(active_agents)
+    }
+
+    /// Get the latest AgentReference of each agent in the given time span
+    fn get_latest_agent_references(from: DateTime<Utc>, until: DateTime<Utc>) -> SocialContextResult<Vec<AgentReference>> {
+        let agent_references = SocialContextDNA::get_agent_references(from, until)?;
+        let agents = agent_references
+            .iter()
+            .map(|val| val.agent.clone())
+            .collect::<Vec<AgentPubKey>>();
+
+        Ok(dedup(&agents)
+            .into_iter()
+            .filter_map(|agent| {
+                agent_references
+                    .iter()
+                    .filter(|agent_ref| agent_ref.agent == agent)
+                    .max_by_key(|agent_ref| agent_ref.timestamp)
+                    .cloned()
+            })
+            .collect())
+    }
+
+    fn get_agent_references(from: DateTime<Utc>, until: DateTime<Utc>) -> SocialContextResult<Vec<AgentReference>> {
+        Ok(hc_time_index::get_links_and_load_for_time_span::<AgentReference>(
             String::from("active_agent"),
-            now - *ACTIVE_AGENT_DURATION,
-            now,
+            from,
+            until,
             Some(LinkTag::new("")),
             SearchStrategy::Bfs,
             None,
-        )?;
+        )?)
+    }
 
-        let current_agent_online = recent_agents
-            .iter()
-            .find(|agent| {
-                agent.agent
-                    == agent_info()
-                        .expect("Could not get agent info")
-                        .agent_latest_pubkey
-            });
-        match current_agent_online {
-            Some(agent_ref) => {
-                //If the agent is already marked online then return the timestamp of them being online so the zome caller can add another active_agent link at the correct time in the future
-                //But for now this is TODO and we will just add an agent reference anyway
-                let new_agent_ref = AgentReference {
-                    agent: agent_info()?.agent_initial_pubkey,
-                    timestamp: now,
-                };
-                create_entry(&new_agent_ref)?;
-                hc_time_index::index_entry(
-                    String::from("active_agent"),
-                    new_agent_ref,
-                    LinkTag::new(""),
-                )?;
-                Ok(Some(agent_ref.timestamp))
-            },
-            None => {
-                //Agent is not marked online so lets add an online agent reference
+    /// Mark the current agent as online for ACTIVE_AGENT_DURATION with an optional presence status & link subscriptions.
+    /// If the agent already has an AgentReference with the same status & subscriptions in the active window then nothing is committed and the timestamp of that reference is returned;
+    /// the agent stays online until that timestamp + ACTIVE_AGENT_DURATION, which is when the next call should be made to extend their presence
+    pub fn add_active_agent_link(input: AddActiveAgentLinkInput) -> SocialContextResult<Option<DateTime<Utc>>> {
+        let now = get_now()?;
+        let agent = agent_info()?.agent_latest_pubkey;
+
+        //Get the latest reference of the current agent; a reference made exactly ACTIVE_AGENT_DURATION ago is expiring so is not counted
+        let existing = SocialContextDNA::get_agent_references(now - *ACTIVE_AGENT_DURATION, now)?
+            .into_iter()
+            .filter(|agent_ref| agent_ref.agent == agent && agent_ref.timestamp > now - *ACTIVE_AGENT_DURATION)
+            .max_by_key(|agent_ref| agent_ref.timestamp);
+
+        match existing {
+            Some(existing) if existing.status == input.status && existing.subscriptions == input.subscriptions => Ok(Some(existing.timestamp)),
+            _ => {
+                //Agent is not marked online or has changed their status or subscriptions so lets add an online agent reference
                 let agent_ref = AgentReference {
-                    agent: agent_info()?.agent_initial_pubkey,
+                    agent: agent.clone(),
                     timestamp: now,
+                    status: input.status.clone(),
+                    subscriptions: input.subscriptions,
                 };
                 create_entry(&agent_ref)?;
                 hc_time_index::index_entry(
@@ -96,14 +356,80 @@ impl SocialContextDNA {
                     agent_ref,
                     LinkTag::new(""),
                 )?;
+                SocialContextDNA::send_signal(SocialContextSignal::PresenceChanged { agent, online: true, status: input.status })?;
                 Ok(None)
             }
         }
     }
 
-    pub fn index_link(input: AddLinkInput) -> SocialContextResult<()> {
-        //Check the index_strategy passed in the call and generate appropriate number of link permutations
-        let link_indexes = match input.index_strategy {
+    /// Withdraw the current agent's AgentReference(s) from the active agent time index so they are immediately treated as offline
+    pub fn remove_active_agent_link() -> SocialContextResult<()> {
+        let now = get_now()?;
+        let agent = agent_info()?.agent_latest_pubkey;
+
+        let agent_refs = SocialContextDNA::get_agent_references(now - *ACTIVE_AGENT_DURATION, now)?
+            .into_iter()
+            .filter(|agent_ref| agent_ref.agent == agent);
+        for agent_ref in agent_refs {
+            let agent_ref_hash = agent_ref.hash()?;
+            hc_time_index::remove_index(agent_ref_hash.clone())?;
+            if let Some(element) = get(agent_ref_hash, GetOptions::latest())? {
+                delete_entry(element.header_address().to_owned())?;
+            };
+        }
+        SocialContextDNA::send_signal(SocialContextSignal::PresenceChanged { agent, online: false, status: None })
+    }
+
+    pub fn add_did_binding(input: AddDidBindingInput) -> SocialContextResult<HeaderHash> {
+        //Bind the DID to the agent making this call; validation ensures agent can only bind DID(s) to their own key, signed by the DID key
+        let binding = DidBinding {
+            did: input.did.clone(),
+            agent: agent_info()?.agent_latest_pubkey,
+            signature: input.signature,
+        };
+        let header_hash = create_entry(&binding)?;
+
+        //Index the binding from the DID so agents for a given DID can be discovered
+        let did_path = Path::from(format!("did{}", input.did));
+        did_path.ensure()?;
+        create_link(did_path.path_entry_hash()?, hash_entry(&binding)?, LinkTag::new("did_binding"))?;
+        Ok(header_hash)
+    }
+
+    /// Get the header of a DidBinding of the DID to the current agent
+    fn get_own_did_binding(did: &str) -> SocialContextResult<Option<HeaderHash>> {
+        let agent = agent_info()?.agent_latest_pubkey;
+        let did_path = Path::from(format!("did{}", did));
+        for link in hdk::link::get_links(did_path.path_entry_hash()?, Some(LinkTag::new("did_binding")))? {
+            if let Some(element) = get(link.target, GetOptions::latest())? {
+                if let Some(binding) = element.entry().to_app_option::<DidBinding>()? {
+                    if binding.did == did && binding.agent == agent {
+                        return Ok(Some(element.header_address().to_owned()));
+                    };
+                };
+            };
+        }
+        Ok(None)
+    }
+
+    pub fn get_did_agents(did: String) -> SocialContextResult<Vec<AgentPubKey>> {
+        let did_path = Path::from(format!("did{}", did));
+        let agents = hdk::link::get_links(did_path.path_entry_hash()?, Some(LinkTag::new("did_binding")))?
+            .into_iter()
+            .map(|link| match get(link.target, GetOptions::latest())? {
+                Some(element) => Ok(element.entry().to_app_option::<DidBinding>()?.map(|binding| binding.agent)),
+                None => Ok(None),
+            })
+            .collect::<SocialContextResult<Vec<Option<AgentPubKey>>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<AgentPubKey>>();
+        Ok(dedup(&agents))
+    }
+
+    /// Check the index_strategy passed in the call and generate appropriate number of link permutations
+    fn get_link_indexes(input: &AddLinkInput) -> SocialContextResult<Vec<LinkPermutation>> {
+        Ok(match input.index_strategy {
             //Index strategy is full so we generate all possible indexes to fufill all query possibilities +
             //add another wildcard index to make this discoverable when querying with no source, predicate or target 
             IndexStrategy::FullWithWildCard => {
@@ -129,16 +455,21 @@ impl SocialContextDNA {
                         "Expected predicate with simple index strategy",
                     ))?),
             )]
-        };
+        })
+    }
 
-        //For each LinkPermutation index the entry
+    /// Index the LinkExpression under each LinkPermutation, in the IndexMode recorded in the LinkExpression.
+    /// Every LinkExpression is also written to the "link_sync" time index so get_links_since finds it whatever its IndexMode & IndexStrategy
+    fn index_link(link_expression: &LinkExpression, link_indexes: Vec<LinkPermutation>) -> SocialContextResult<()> {
+        hc_time_index::index_entry(String::from("link_sync"), link_expression.clone(), LinkTag::new(""))?;
+        let index_mode = IndexMode::or_default(link_expression.index_mode.clone());
         for link_index in link_indexes {
-            if *ENABLE_TIME_INDEX {
+            if index_mode == IndexMode::TimeIndex {
                 //Create index using hc_time_index crate and put it into a time tree to allow for retreival of links by time as well as source, predicate, target (IndexStrategy dependant)
-                hc_time_index::index_entry(link_index.root_index, input.link_expression.clone(), link_index.tag)?;
+                hc_time_index::index_entry(link_index.root_index, link_expression.clone(), link_index.tag)?;
             } else {
                 //Create basic index (link) which links from Path() entry -> link_index.tag -> LinkExpression 
-                let link_hash = hash_entry(&input.link_expression)?;
+                let link_hash = hash_entry(link_expression)?;
                 let path_source = Path::from(link_index.root_index);
                 path_source.ensure()?;
                 create_link(path_source.path_entry_hash()?, link_hash.clone(), link_index.tag)?;
@@ -147,50 +478,264 @@ impl SocialContextDNA {
         Ok(())
     }
 
-    pub fn get_links(get_links: GetLinks) -> SocialContextResult<Vec<LinkExpression>> {
+    pub fn get_links(get_links: GetLinks) -> SocialContextResult<GetLinksResponse> {
+        let query = get_links.triple.clone();
         let link_query_elements = get_link_permutation_by(get_links.triple);
 
-        //TODO: this should be specified by the zome caller and not in DNA props
-        if *ENABLE_TIME_INDEX {
-            //If fromDate & untilDate have been supplied then call hc_time_index looking for LinkExpression(s) in date range with given root_index + tag
-            if get_links.from_date.is_some() && get_links.until_date.is_some() {
-                Ok(hc_time_index::get_links_and_load_for_time_span::<
-                    LinkExpression,
-                >(
-                    link_query_elements.root_index,
-                    get_links.from_date.unwrap(),
-                    get_links.until_date.unwrap(),
-                    Some(link_query_elements.tag),
-                    SearchStrategy::Dfs,
-                    get_links.limit,
-                )?)
-            } else {
-                //fromDate & untilDate not supplied so we will try to get all LinkExpression(s) from now -> unix epoch
-                //This will return all links, since the hc_time_index crate does not support indexing before unix epoch currently
-                let now = sys_time()?.as_seconds_and_nanos();
-                let now = DateTime::<Utc>::from_utc(
-                    NaiveDateTime::from_timestamp(now.0, now.1),
-                    Utc,
-                );
-                let unix = DateTime::<Utc>::from_utc(
-                    NaiveDateTime::from_timestamp(0, 0),
-                    Utc,
-                );
-                Ok(hc_time_index::get_links_and_load_for_time_span::<
-                    LinkExpression,
-                >(
-                    link_query_elements.root_index,
-                    unix,
-                    now,
-                    Some(link_query_elements.tag),
-                    SearchStrategy::Bfs,
-                    get_links.limit,
-                )?)
-            }
+        let order = resolve_order(get_links.from_date, get_links.until_date, get_links.order);
+        let time_span = match (get_links.from_date, get_links.until_date) {
+            (Some(from_date), Some(until_date)) => Some(arrange_time_span(from_date, until_date, order)),
+            _ => None,
+        };
+
+        //If a cursor was supplied then continue the time span from the last link of the previous page
+        let cursor = match get_links.cursor {
+            Some(cursor) => Some(LinkCursor::decode(&cursor)?),
+            None => None,
+        };
+        let time_span = match (&cursor, time_span) {
+            (Some(cursor), Some((_, end))) => Some((cursor.timestamp, end)),
+            (Some(cursor), None) => match order {
+                Order::Ascending => Some((cursor.timestamp, get_now()?)),
+                Order::Descending => Some((cursor.timestamp, unix_epoch())),
+            },
+            (None, time_span) => time_span,
+        };
+        //The cursor link & the links before it at the same timestamp are loaded again at the start of the time span, they are skipped below
+
+        let mut links = if IndexMode::or_default(get_links.index_mode) == IndexMode::TimeIndex {
+            //Call hc_time_index looking for LinkExpression(s) in the time span, in the direction of the order, with given root_index + tag
+            //When fromDate & untilDate are not supplied we will try to get all LinkExpression(s) between now & unix epoch
+            //This will return all links, since the hc_time_index crate does not support indexing before unix epoch currently
+            let (time_span, whole_index) = match time_span {
+                Some(time_span) => (time_span, false),
+                None => (arrange_time_span(unix_epoch(), get_now()?, order), true),
+            };
+            SocialContextDNA::load_time_index_links(
+                link_query_elements,
+                time_span,
+                whole_index,
+                order,
+                &query,
+                cursor.as_ref(),
+                get_links.limit,
+            )?
         } else {
-            //Time index not enabled so just make a simple query
-            SocialContextDNA::make_simple_link_query(Path::from(link_query_elements.root_index).path_entry_hash()?, Some(link_query_elements.tag))
+            //Time index not enabled so just make a simple query and apply the time span & order to the loaded LinkExpression(s)
+            let links = SocialContextDNA::make_simple_link_query(Path::from(link_query_elements.root_index).path_entry_hash()?, Some(link_query_elements.tag))?
+                .into_iter()
+                .filter(|link| triple_matches(&query, &link.data))
+                .collect();
+            filter_by_time_span(links, time_span, order)
+        };
+
+        //Skip all links up to and including the cursor link, only then apply the limit so that pages are always filled
+        if let Some(cursor) = &cursor {
+            let skipped = cursor.skip_count(&links, order)?;
+            links.drain(..skipped);
+        };
+        if let Some(limit) = get_links.limit {
+            links.truncate(limit);
+        };
+
+        //Pages are only short once all matching links have been loaded, so a cursor is only needed when the page is full
+        let cursor = match (get_links.limit, links.last()) {
+            (Some(limit), Some(last)) if links.len() == limit => Some(LinkCursor::for_link(last)?.encode()?),
+            _ => None,
+        };
+        Ok(GetLinksResponse { links, cursor })
+    }
+
+    pub fn get_links_since(input: GetLinksSince) -> SocialContextResult<GetLinksSinceResponse> {
+        //A limited call is continued right after its last change, a complete sync is continued from sync_overlap_s before it ended
+        let (since, position) = match (input.cursor, input.timestamp) {
+            (Some(cursor), _) => {
+                let cursor = SyncCursor::decode(&cursor)?;
+                match cursor.last_change {
+                    Some(_) => (cursor.timestamp, Some(cursor)),
+                    None => (cursor.timestamp - *SYNC_OVERLAP, None),
+                }
+            }
+            (None, Some(timestamp)) => (timestamp, None),
+            (None, None) => {
+                return Err(SocialContextError::RequestError(
+                    "Expected timestamp or cursor for get_links_since",
+                ))
+            }
+        };
+        let now = get_now()?;
+
+        //Additions are found by LinkExpression timestamp in the sync index every link is written to
+        let mut changes = SocialContextDNA::load_sync_changes(since, now, position.as_ref(), input.limit, |from, until, limit| {
+            Ok(hc_time_index::get_links_and_load_for_time_span::<LinkExpression>(
+                String::from("link_sync"),
+                from,
+                until,
+                Some(LinkTag::new("")),
+                SearchStrategy::Dfs,
+                limit,
+            )?
+            .into_iter()
+            .map(|link| SyncChange { timestamp: link.timestamp, removed: false, link })
+            .collect())
+        })?;
+        //Removals are found by the time the LinkExpression was removed
+        changes.extend(SocialContextDNA::load_sync_changes(since, now, position.as_ref(), input.limit, |from, until, limit| {
+            Ok(hc_time_index::get_links_and_load_for_time_span::<LinkRemoval>(
+                String::from("link_removal"),
+                from,
+                until,
+                Some(LinkTag::new("")),
+                SearchStrategy::Dfs,
+                limit,
+            )?
+            .into_iter()
+            .map(|removal| SyncChange { timestamp: removal.timestamp, removed: true, link: removal.link })
+            .collect())
+        })?);
+
+        changes.sort_by(|a, b| a.key().cmp(&b.key()));
+        if let Some(position) = &position {
+            changes.retain(|change| position.is_before(change));
+        };
+        if let Some(limit) = input.limit {
+            changes.truncate(limit);
+        };
+
+        //Like get_links, a page is only full when there may be more changes to load
+        let has_more = input.limit.map_or(false, |limit| changes.len() == limit);
+        let cursor = match changes.last() {
+            Some(last) if has_more => SyncCursor::after(last),
+            _ => SyncCursor { timestamp: now, last_change: None },
+        };
+        let (removed, added): (Vec<SyncChange>, Vec<SyncChange>) = changes.into_iter().partition(|change| change.removed);
+        Ok(GetLinksSinceResponse {
+            added: added.into_iter().map(|change| change.link).collect(),
+            removed: removed.into_iter().map(|change| change.link).collect(),
+            cursor: cursor.encode()?,
+            has_more,
+        })
+    }
+
+    /// Load the changes of one time index in ascending order, using the given loader for a time span & batch limit.
+    /// Further batches are loaded from the last loaded timestamp until `limit` changes after the cursor position are loaded or the time span is exhausted
+    fn load_sync_changes<F>(
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        position: Option<&SyncCursor>,
+        limit: Option<usize>,
+        load: F,
+    ) -> SocialContextResult<Vec<SyncChange>>
+    where
+        F: Fn(DateTime<Utc>, DateTime<Utc>, Option<usize>) -> SocialContextResult<Vec<SyncChange>>,
+    {
+        let mut start = since;
+        let mut batch_limit = limit;
+        let mut loaded = HashSet::new();
+        let mut changes: Vec<SyncChange> = vec![];
+        loop {
+            let batch = load(start, until, batch_limit)?;
+            let exhausted = batch_limit.map_or(true, |batch_limit| batch.len() < batch_limit);
+            let last_timestamp = batch.iter().map(|change| change.timestamp).max();
+            for change in batch {
+                //Batches overlap at the timestamp they continue from
+                if loaded.insert(change.link.hash()?) {
+                    changes.push(change);
+                };
+            }
+            let after_position = changes
+                .iter()
+                .filter(|change| position.map_or(true, |position| position.is_before(change)))
+                .count();
+            if exhausted || limit.map_or(true, |limit| after_position >= limit) {
+                break;
+            };
+            //If a whole batch shares the timestamp it started from, load a bigger batch instead of moving forward
+            match last_timestamp {
+                Some(timestamp) if timestamp != start => start = timestamp,
+                _ => batch_limit = batch_limit.map(|batch_limit| batch_limit * 2),
+            };
+        }
+        Ok(changes)
+    }
+
+    /// Load the LinkExpression(s) exactly matching the query from the time index, in the given order.
+    /// Index tags are matched as prefixes, so loaded links are filtered before they count towards the limit and
+    /// further batches are loaded from the last loaded timestamp until the limit is reached or the time span is exhausted
+    fn load_time_index_links(
+        index: LinkPermutation,
+        time_span: (DateTime<Utc>, DateTime<Utc>),
+        whole_index: bool,
+        order: Order,
+        query: &Triple,
+        cursor: Option<&LinkCursor>,
+        limit: Option<usize>,
+    ) -> SocialContextResult<Vec<LinkExpression>> {
+        let (mut start, end) = time_span;
+        let mut batch_limit = limit;
+        let mut loaded = HashSet::new();
+        let mut links = vec![];
+        loop {
+            //Bfs reaches the earliest links first when scanning the whole index in ascending order, otherwise Dfs follows the direction of the time span
+            let strategy = match (whole_index, order) {
+                (true, Order::Ascending) => SearchStrategy::Bfs,
+                _ => SearchStrategy::Dfs,
+            };
+            let mut batch = hc_time_index::get_links_and_load_for_time_span::<LinkExpression>(
+                index.root_index.clone(),
+                start,
+                end,
+                Some(index.tag.clone()),
+                strategy,
+                batch_limit,
+            )?;
+            let exhausted = batch_limit.map_or(true, |batch_limit| batch.len() < batch_limit);
+            sort_links(&mut batch, order);
+            let last_timestamp = batch.last().map(|link| link.timestamp);
+            for link in batch {
+                //Batches overlap at the timestamp they continue from
+                if loaded.insert(link.hash()?) && triple_matches(query, &link.data) {
+                    links.push(link);
+                };
+            }
+            sort_links(&mut links, order);
+            //Links up to & including the cursor link are skipped by the caller so do not count towards the limit
+            let skipped = match cursor {
+                Some(cursor) => cursor.skip_count(&links, order)?,
+                None => 0,
+            };
+            if exhausted || limit.map_or(true, |limit| links.len() - skipped >= limit) {
+                break;
+            };
+            //If a whole batch shares the timestamp it started from, load a bigger batch instead of moving forward
+            match last_timestamp {
+                Some(timestamp) if timestamp != start => start = timestamp,
+                _ => batch_limit = batch_limit.map(|batch_limit| batch_limit * 2),
+            };
         }
+        Ok(links)
+    }
+
+    /// Get the entry hashes of all LinkExpression(s) matching the full triple
+    pub fn link_exists(triple: Triple) -> SocialContextResult<Vec<EntryHash>> {
+        if triple.source.is_none() || triple.target.is_none() || triple.predicate.is_none() {
+            return Err(SocialContextError::RequestError(
+                "Expected source, target and predicate for link existence check",
+            ));
+        };
+        SocialContextDNA::get_links(GetLinks {
+            triple,
+            from_date: None,
+            until_date: None,
+            limit: None,
+            index_mode: None,
+            cursor: None,
+            order: None,
+        })?
+        .links
+        .iter()
+        .map(|link| Ok(link.hash()?))
+        .collect()
     }
 
     fn make_simple_link_query(
@@ -225,45 +770,139 @@ impl SocialContextDNA {
     }
 
     pub fn remove_link(link: LinkExpression) -> SocialContextResult<()> {
-        //Get the LinkExpression entry to be deleted
-        let entry =
-            get(link.hash()?, GetOptions::latest())?.ok_or(SocialContextError::RequestError(
+        SocialContextDNA::remove_links(vec![link])
+    }
+
+    pub fn remove_links(links: Vec<LinkExpression>) -> SocialContextResult<()> {
+        let hashes = links
+            .iter()
+            .map(SocialContextDNA::get_committed_link_hash)
+            .collect::<SocialContextResult<Vec<EntryHash>>>()?;
+        SocialContextDNA::remove_links_by_hash(hashes)
+    }
+
+    /// Get the entry hash of the committed LinkExpression that a zome caller refers to.
+    /// add_link records the index mode, permissions version & DID binding in the committed entry, so the expression as the caller submitted it may not hash to that entry;
+    /// in that case the committed entry is looked up through the indexes of its triple at its timestamp
+    fn get_committed_link_hash(link: &LinkExpression) -> SocialContextResult<EntryHash> {
+        let link_hash = link.hash()?;
+        if get(link_hash.clone(), GetOptions::latest())?.is_some() {
+            return Ok(link_hash);
+        };
+        let index_modes = match &link.index_mode {
+            Some(index_mode) => vec![index_mode.clone()],
+            None => vec![IndexMode::Path, IndexMode::TimeIndex],
+        };
+        for index_mode in index_modes {
+            let candidates = SocialContextDNA::get_links(GetLinks {
+                triple: link.data.clone(),
+                from_date: Some(link.timestamp),
+                until_date: Some(link.timestamp),
+                limit: None,
+                index_mode: Some(index_mode),
+                cursor: None,
+                order: None,
+            })?
+            .links;
+            if let Some(committed) = candidates.iter().find(|committed| committed.is_committed_form_of(link)) {
+                return Ok(committed.hash()?);
+            };
+        }
+        Err(SocialContextError::RequestError(
+            "Could not find link expression that was requested for deletion",
+        ))
+    }
+
+    pub fn remove_links_by_hash(link_hashes: Vec<EntryHash>) -> SocialContextResult<()> {
+        let removed = SocialContextDNA::delete_links(link_hashes)?;
+        SocialContextDNA::send_link_signal(SocialContextSignal::Removed(removed))
+    }
+
+    /// Delete the LinkExpression entries and all of their indexes, returning the deleted LinkExpression(s)
+    fn delete_links(link_hashes: Vec<EntryHash>) -> SocialContextResult<Vec<LinkExpression>> {
+        //Get the LinkExpression entries to be deleted
+        let agent = agent_info()?.agent_latest_pubkey;
+        let mut entries = vec![];
+        for link_hash in link_hashes {
+            let element = get(link_hash.clone(), GetOptions::latest())?.ok_or(SocialContextError::RequestError(
                 "Could not find link expression that was requested for deletion",
             ))?;
+            let link = element.entry().to_app_option::<LinkExpression>()?.ok_or(
+                SocialContextError::RequestError("Entry requested for deletion is not a link expression"),
+            )?;
+            //Check the agent may delete the LinkExpression, otherwise the delete would be rejected in validation
+            if element.header().author() != &agent && !can_moderate(&agent, &link)? {
+                return Err(SocialContextError::PermissionDenied(String::from(
+                    "Only the author of a link, admins or moderators can remove it",
+                )));
+            };
+            entries.push((link_hash, link, element.header_address().to_owned()));
+        }
 
-        if *ENABLE_TIME_INDEX {
-            hc_time_index::remove_index(link.hash()?)?;
-        } else {
-            //Generate the link indexes that are possible for this LinkExpression
+        //Group the LinkExpression(s) by each path index they could be found under, so that each index is only walked once
+        let wildcard = get_wildcard();
+        let mut path_indexes: BTreeMap<(String, Vec<u8>), Vec<EntryHash>> = BTreeMap::new();
+        for (link_hash, link, _) in entries.iter().filter(|(_, link, _)| link.index_mode != Some(IndexMode::TimeIndex)) {
             let mut link_indexes = generate_link_path_permutations(&link.data)?;
-            let wildcard = get_wildcard();
             link_indexes.push(LinkPermutation::new(wildcard.to_string(), wildcard.to_string()));
-
-            let link_hash = link.hash()?;
-            //For each permutation get links on source and if exists then delete where target of link == target LinkExpression to be deleted
             for link_index in link_indexes {
-                let path_source = Path::from(link_index.root_index);
-                hdk::link::get_links(path_source.path_entry_hash()?, Some(link_index.tag))?
-                    .into_iter()
-                    .filter(|link| link.target == link_hash)
-                    .map(|val| {
-                        delete_link(val.create_link_hash.to_owned())?;
-                        Ok(())
-                    })
-                    .collect::<SocialContextResult<()>>()?;
+                path_indexes
+                    .entry((link_index.root_index, link_index.tag.0))
+                    .or_insert_with(Vec::new)
+                    .push(link_hash.clone());
             }
         }
-        delete_entry(entry.header_address().to_owned())?;
-        Ok(())
+
+        //For each path index get links and delete those whose target is a LinkExpression to be deleted
+        let mut removed_path_index = HashSet::new();
+        for ((root_index, tag), targets) in path_indexes {
+            let path_source = Path::from(root_index);
+            for index_link in hdk::link::get_links(path_source.path_entry_hash()?, Some(LinkTag::new(tag)))?
+                .into_iter()
+                .filter(|link| targets.contains(&link.target))
+            {
+                delete_link(index_link.create_link_hash)?;
+                removed_path_index.insert(index_link.target);
+            }
+        }
+
+        let now = get_now()?;
+        let mut removed = vec![];
+        for (link_hash, link, header_hash) in entries {
+            //Links with a recorded IndexMode are always in the "link_sync" time index; links committed before it was recorded are only ever
+            //indexed by one IndexMode, so if they had no path indexes they must be in the time index
+            let time_indexed = match link.index_mode {
+                Some(_) => true,
+                None => !removed_path_index.contains(&link_hash),
+            };
+            if time_indexed {
+                hc_time_index::remove_index(link_hash)?;
+            };
+            delete_entry(header_hash.clone())?;
+
+            //Leave a record of the removal so agents syncing with get_links_since can see it
+            let removal = LinkRemoval {
+                link: link.clone(),
+                link_header: header_hash,
+                timestamp: now,
+            };
+            create_entry(&removal)?;
+            hc_time_index::index_entry(String::from("link_removal"), removal, LinkTag::new(""))?;
+            removed.push(link);
+        }
+        Ok(removed)
     }
 
     pub fn update_link(update_link_input: UpdateLinkInput) -> SocialContextResult<()> {
-        SocialContextDNA::remove_link(update_link_input.source)?;
+        let old = update_link_input.source;
+        let new = update_link_input.target;
+        SocialContextDNA::delete_links(vec![SocialContextDNA::get_committed_link_hash(&old)?])?;
         let add_link_input = AddLinkInput {
-            link_expression: update_link_input.target,
+            link_expression: new.clone(),
             index_strategy: update_link_input.index_strategy,
+            index_mode: update_link_input.index_mode,
         };
-        SocialContextDNA::add_link(add_link_input)?;
-        Ok(())
+        let new = SocialContextDNA::commit_link(add_link_input)?;
+        SocialContextDNA::send_link_signal(SocialContextSignal::Updated { old, new })
     }
 }
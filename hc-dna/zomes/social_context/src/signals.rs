@@ -0,0 +1,110 @@
+use hdk::prelude::*;
+
+use crate::inputs::{PresenceStatus, TriplePattern};
+use crate::utils::pattern_matches;
+use crate::LinkExpression;
+
+/// Version of the signal schema sent by this DNA; bump when the shape of an existing SocialContextSignal variant changes
+pub const SIGNAL_SCHEMA_VERSION: u32 = 1;
+
+/// Versioned wrapper around every signal sent between agents and emitted to the UI
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct SignalEnvelope {
+    pub version: u32,
+    pub signal: SocialContextSignal,
+}
+
+/// Only the version of a signal envelope, used to describe signals that could not be decoded
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+pub struct SignalVersion {
+    pub version: u32,
+}
+
+/// Signal sent to active agents whenever LinkExpression(s) in the social context or agent presence change
+#[derive(Serialize, Deserialize, Clone, SerializedBytes, Debug)]
+#[serde(tag = "type", content = "payload")]
+pub enum SocialContextSignal {
+    Added(Vec<LinkExpression>),
+    Removed(Vec<LinkExpression>),
+    Updated {
+        old: LinkExpression,
+        new: LinkExpression,
+    },
+    /// An agent has come online, changed their status or explicitly gone offline
+    PresenceChanged {
+        agent: AgentPubKey,
+        online: bool,
+        status: Option<PresenceStatus>,
+    },
+    /// Short lived event such as typing indicators or cursor positions, which is never committed
+    Ephemeral {
+        sender: AgentPubKey,
+        kind: String,
+        payload: String,
+    },
+    /// Ephemeral event sent only to a chosen set of agents
+    Direct {
+        sender: AgentPubKey,
+        kind: String,
+        payload: String,
+    },
+    /// Never sent; emitted to the UI when a signal from a newer or incompatible agent could not be decoded
+    Unknown,
+}
+
+impl SignalEnvelope {
+    pub fn new(signal: SocialContextSignal) -> SignalEnvelope {
+        SignalEnvelope {
+            version: SIGNAL_SCHEMA_VERSION,
+            signal,
+        }
+    }
+
+    /// Decode a remote signal, falling back to an Unknown signal instead of failing so that new signal kinds do not break older agents
+    pub fn decode(signal: SerializedBytes) -> SignalEnvelope {
+        match SignalEnvelope::try_from(signal.clone()) {
+            Ok(envelope) => envelope,
+            Err(_) => SignalEnvelope {
+                version: SignalVersion::try_from(signal)
+                    .map(|version| version.version)
+                    .unwrap_or(0),
+                signal: SocialContextSignal::Unknown,
+            },
+        }
+    }
+}
+
+impl SocialContextSignal {
+    /// Narrow a link signal to what an agent with the given subscriptions is interested in, or None if nothing matches.
+    /// Signals which are not about links are passed through unchanged
+    pub fn filter_for(&self, subscriptions: &[TriplePattern]) -> Option<SocialContextSignal> {
+        let link_matches = |link: &LinkExpression| {
+            subscriptions
+                .iter()
+                .any(|pattern| pattern_matches(pattern, &link.data))
+        };
+        let matching = |links: &Vec<LinkExpression>| {
+            links
+                .iter()
+                .filter(|link| link_matches(link))
+                .cloned()
+                .collect::<Vec<LinkExpression>>()
+        };
+        match self {
+            SocialContextSignal::Added(links) => Some(matching(links))
+                .filter(|links| !links.is_empty())
+                .map(SocialContextSignal::Added),
+            SocialContextSignal::Removed(links) => Some(matching(links))
+                .filter(|links| !links.is_empty())
+                .map(SocialContextSignal::Removed),
+            SocialContextSignal::Updated { old, new } => {
+                if link_matches(old) || link_matches(new) {
+                    Some(self.clone())
+                } else {
+                    None
+                }
+            }
+            _ => Some(self.clone()),
+        }
+    }
+}
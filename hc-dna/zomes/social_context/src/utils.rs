@@ -1,5 +1,8 @@
-use crate::inputs::Triple;
+use crate::inputs::{AccessRules, Order, Triple, TriplePattern};
+use crate::LinkExpression;
+use chrono::{DateTime, SecondsFormat, Utc};
 use hdk::prelude::*;
+use k256::ecdsa::{Signature as Secp256k1Signature, VerifyingKey};
 
 use std::hash::Hash;
 use std::collections::HashSet;
@@ -19,6 +22,21 @@ impl LinkPermutation {
     }
 }
 
+/// A LinkExpression added or removed at the given time, as returned by get_links_since
+pub(crate) struct SyncChange {
+    pub timestamp: DateTime<Utc>,
+    pub removed: bool,
+    pub link: LinkExpression,
+}
+
+impl SyncChange {
+    /// Changes are sorted by time, additions before removals, and then by the link_sort_key of their LinkExpression
+    pub(crate) fn key(&self) -> (DateTime<Utc>, bool, &str, &Option<String>, &Option<String>, &Option<String>) {
+        let (_, author, source, target, predicate) = link_sort_key(&self.link);
+        (self.timestamp, self.removed, author, source, target, predicate)
+    }
+}
+
 pub(crate) fn get_wildcard() -> &'static str {
     "*"
 }
@@ -94,15 +112,20 @@ pub(crate) fn generate_link_path_permutations(
 }
 
 /// Derive the source link index value and link tag value to query with based on the values passed in GetLinks.triple
-/// Note we are only looking for two or one elements in the triple, since if you have three you already have the LinkExpression! 
+/// When all three elements are supplied the query is an existence check; results are narrowed to the full triple by the caller
 pub(crate) fn get_link_permutation_by(triple: Triple) -> LinkPermutation {
     let wildcard = get_wildcard();
     let Triple { source, target, predicate } = triple;
     
     match (source, target, predicate) {
+        //Query with source + target + predicate; use the source + predicate index since it exists for every IndexStrategy
+        (Some(source), Some(_), Some(predicate)) => LinkPermutation::new(
+            format!("s{}", source),
+            format!("p{}", predicate),
+        ),
         //Query with source + target; will match all LinkExpression with same source + target
         //In this case the predicate unknown here and thus the value zome caller is interested in
-        (Some(source), Some(target), _) => LinkPermutation::new(
+        (Some(source), Some(target), None) => LinkPermutation::new(
             format!("s{}", source),
             format!("t{}", target),
         ),
@@ -138,6 +161,213 @@ pub(crate) fn get_link_permutation_by(triple: Triple) -> LinkPermutation {
     }
 }
 
+/// Check that each element supplied in the query triple is exactly equal to that of the given triple.
+/// Index tags are matched as prefixes by get_links so this is used to drop links which only share a prefix with the query
+pub(crate) fn triple_matches(query: &Triple, triple: &Triple) -> bool {
+    let element_matches = |query: &Option<String>, value: &Option<String>| match query {
+        Some(query) => value.as_ref() == Some(query),
+        None => true,
+    };
+    element_matches(&query.source, &triple.source)
+        && element_matches(&query.target, &triple.target)
+        && element_matches(&query.predicate, &triple.predicate)
+}
+
+/// Check if a triple matches a link signal subscription pattern
+pub(crate) fn pattern_matches(pattern: &TriplePattern, triple: &Triple) -> bool {
+    let source_matches = match &pattern.source {
+        Some(source) => triple.source.as_ref() == Some(source),
+        None => true,
+    };
+    let predicate_matches = match &pattern.predicate {
+        Some(predicate) => triple.predicate.as_ref() == Some(predicate),
+        None => true,
+    };
+    let target_matches = match &pattern.target_prefix {
+        Some(prefix) => triple.target.as_ref().map_or(false, |target| target.starts_with(prefix.as_str())),
+        None => true,
+    };
+    source_matches && predicate_matches && target_matches
+}
+
+/// Public key of a did:key DID that LinkExpression proofs are verified with
+pub(crate) enum ProofKey {
+    //Signs the msgpack serialized LinkExpressionProofData
+    Ed25519(AgentPubKey),
+    //Used by AD4M agents; signs the JSON of the LinkExpression data & timestamp
+    Secp256k1(VerifyingKey),
+}
+
+/// Resolve the public key of a did:key DID (multibase base58btc) with the ed25519 or secp256k1 multicodec
+pub(crate) fn resolve_proof_key(did: &str) -> Option<ProofKey> {
+    let encoded = did.strip_prefix("did:key:z")?;
+    let bytes = bs58::decode(encoded).into_vec().ok()?;
+    match bytes.split_at(2.min(bytes.len())) {
+        ([0xed, 0x01], key) if key.len() == 32 => Some(ProofKey::Ed25519(agent_pub_key_from_raw_32(key))),
+        ([0xe7, 0x01], key) if key.len() == 33 => VerifyingKey::from_sec1_bytes(key).ok().map(ProofKey::Secp256k1),
+        _ => None,
+    }
+}
+
+/// Build an agent public key from a raw 32 byte ed25519 key by appending its DHT location bytes
+pub(crate) fn agent_pub_key_from_raw_32(key: &[u8]) -> AgentPubKey {
+    let mut raw = key.to_vec();
+    raw.extend(holo_hash::encode::holo_dht_location_bytes(key));
+    AgentPubKey::from_raw_36(raw)
+}
+
+/// Resolve the ed25519 public key of a did:key DID as an agent public key
+pub(crate) fn resolve_did_key(did: &str) -> Option<AgentPubKey> {
+    match resolve_proof_key(did)? {
+        ProofKey::Ed25519(key) => Some(key),
+        ProofKey::Secp256k1(_) => None,
+    }
+}
+
+/// Decode a hex encoded 64 byte ed25519 signature
+pub(crate) fn decode_signature(signature: &str) -> Result<Signature, &'static str> {
+    let signature = hex::decode(signature).map_err(|_| "Expected signature to be hex encoded")?;
+    let signature: [u8; 64] = signature
+        .try_into()
+        .map_err(|_| "Expected signature to be 64 bytes")?;
+    Ok(Signature::from(signature))
+}
+
+/// Decode a hex encoded 64 byte (r || s) secp256k1 ECDSA signature
+pub(crate) fn decode_secp256k1_signature(signature: &str) -> Result<Secp256k1Signature, &'static str> {
+    let signature = hex::decode(signature).map_err(|_| "Expected signature to be hex encoded")?;
+    Secp256k1Signature::try_from(signature.as_slice()).map_err(|_| "Expected signature to be a 64 byte secp256k1 signature")
+}
+
+/// The messages an AD4M agent may have signed for a LinkExpression, being JSON.stringify({data, timestamp}).
+/// AD4M links hold "" for missing triple elements while the zome receives them as null, so both "" and an absent element are tried
+pub(crate) fn ad4m_proof_messages(link: &LinkExpression) -> Vec<Vec<u8>> {
+    let elements = [
+        ("source", &link.data.source),
+        ("target", &link.data.target),
+        ("predicate", &link.data.predicate),
+    ];
+    let timestamp = json_string(&link.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true));
+    vec![true, false]
+        .into_iter()
+        .map(|missing_as_empty| {
+            let data = elements
+                .iter()
+                .filter_map(|(name, value)| match value {
+                    Some(value) => Some(format!("\"{}\":{}", name, json_string(value))),
+                    None if missing_as_empty => Some(format!("\"{}\":\"\"", name)),
+                    None => None,
+                })
+                .collect::<Vec<String>>()
+                .join(",");
+            format!("{{\"data\":{{{}}},\"timestamp\":{}}}", data, timestamp).into_bytes()
+        })
+        .collect()
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+/// Check that a proof key refers to the author DID, either as the DID itself or as one of its verification methods
+pub(crate) fn proof_key_of(author: &str, key: &str) -> bool {
+    match key.strip_prefix(author) {
+        Some(fragment) => fragment.is_empty() || fragment.starts_with('#'),
+        None => false,
+    }
+}
+
+/// Get the expression language of a URI, being the scheme before "://"
+pub(crate) fn get_language(uri: &str) -> Option<&str> {
+    uri.split_once("://").map(|(language, _)| language)
+}
+
+/// Check a value against allow & deny rules
+pub(crate) fn rules_permit(rules: &AccessRules, value: &str) -> bool {
+    let allowed = match &rules.allow {
+        Some(allow) => allow.iter().any(|allowed| allowed == value),
+        None => true,
+    };
+    allowed && !rules.deny.iter().any(|denied| denied == value)
+}
+
+/// Resolve the order of get_links results; an explicit order wins, otherwise fromDate after untilDate means descending as in hc_time_index
+pub(crate) fn resolve_order(
+    from_date: Option<DateTime<Utc>>,
+    until_date: Option<DateTime<Utc>>,
+    order: Option<Order>,
+) -> Order {
+    match (order, from_date, until_date) {
+        (Some(order), _, _) => order,
+        (None, Some(from_date), Some(until_date)) if from_date > until_date => Order::Descending,
+        _ => Order::Ascending,
+    }
+}
+
+/// Arrange two dates into a (start, end) time span that runs in the direction of the order
+pub(crate) fn arrange_time_span(a: DateTime<Utc>, b: DateTime<Utc>, order: Order) -> (DateTime<Utc>, DateTime<Utc>) {
+    let (earliest, latest) = if a > b { (b, a) } else { (a, b) };
+    match order {
+        Order::Ascending => (earliest, latest),
+        Order::Descending => (latest, earliest),
+    }
+}
+
+/// Apply the time span & order of a GetLinks query to LinkExpression(s) loaded without the time index.
+/// Mirrors hc_time_index: the time span is inclusive
+pub(crate) fn filter_by_time_span(
+    mut links: Vec<LinkExpression>,
+    time_span: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    order: Order,
+) -> Vec<LinkExpression> {
+    if let Some((a, b)) = time_span {
+        let (start, end) = arrange_time_span(a, b, Order::Ascending);
+        links.retain(|link| link.timestamp >= start && link.timestamp <= end);
+    };
+
+    sort_links(&mut links, order);
+    links
+}
+
+/// Key LinkExpression(s) are sorted by: timestamp and then author & triple, so results are deterministic across agents
+pub(crate) fn link_sort_key(link: &LinkExpression) -> (DateTime<Utc>, &str, &Option<String>, &Option<String>, &Option<String>) {
+    (link.timestamp, link.author.as_str(), &link.data.source, &link.data.target, &link.data.predicate)
+}
+
+/// Sort LinkExpression(s) by their link_sort_key
+pub(crate) fn sort_links(links: &mut Vec<LinkExpression>, order: Order) {
+    links.sort_by(|a, b| link_sort_key(a).cmp(&link_sort_key(b)));
+    if order == Order::Descending {
+        links.reverse();
+    };
+}
+
+pub(crate) fn unix_epoch() -> DateTime<Utc> {
+    DateTime::<Utc>::from_utc(chrono::NaiveDateTime::from_timestamp(0, 0), Utc)
+}
+
+pub(crate) fn get_now() -> ExternResult<DateTime<Utc>> {
+    Ok(to_datetime(sys_time()?))
+}
+
+pub(crate) fn to_datetime(timestamp: Timestamp) -> DateTime<Utc> {
+    let (secs, nanos) = timestamp.as_seconds_and_nanos();
+    DateTime::<Utc>::from_utc(chrono::NaiveDateTime::from_timestamp(secs, nanos), Utc)
+}
+
+/// Check that a timestamp is no further than the allowed skew before or after the reference time
+pub(crate) fn within_skew(timestamp: DateTime<Utc>, reference: DateTime<Utc>, skew: chrono::Duration) -> bool {
+    timestamp >= reference - skew && timestamp <= reference + skew
+}
+
+/// Check if a header commits an app entry of the given entry def
+pub(crate) fn is_entry_of(header: &Header, entry_def: EntryDefIndex) -> bool {
+    match header.entry_type() {
+        Some(EntryType::App(app_entry_type)) => app_entry_type.id() == entry_def,
+        _ => false,
+    }
+}
+
 pub (crate) fn dedup<T: Eq + Hash + Clone>(vs: &Vec<T>) -> Vec<T> {
     let hs = vs.iter().cloned().collect::<HashSet<T>>();
 
@@ -152,6 +382,29 @@ mod tests {
     const TRIPLE_TARGET: &str = "target";
     const TRIPLE_PREDICATE: &str = "predicate";
     const WILDCARD: &str = "*";
+
+    fn link_at(target: &str, timestamp: i64) -> LinkExpression {
+        LinkExpression {
+            author: String::from("author"),
+            data: Triple {
+                source: Some(TRIPLE_SOURCE.to_string()),
+                target: Some(target.to_string()),
+                predicate: Some(TRIPLE_PREDICATE.to_string()),
+            },
+            timestamp: DateTime::<Utc>::from_utc(chrono::NaiveDateTime::from_timestamp(timestamp, 0), Utc),
+            proof: crate::inputs::ExpressionProof {
+                signature: String::from("sig"),
+                key: String::from("key"),
+            },
+            permissions: None,
+            did_binding: None,
+            index_mode: None,
+        }
+    }
+
+    fn targets(links: &[LinkExpression]) -> Vec<String> {
+        links.iter().map(|link| link.data.target.clone().unwrap()).collect()
+    }
     
     #[test]
     fn generate_link_path_permutations_works() {
@@ -255,7 +508,7 @@ mod tests {
             predicate: Some(TRIPLE_PREDICATE.to_string()),
         };
         let result = get_link_permutation_by(triple);
-        assert_eq!(result, LinkPermutation::new(TRIPLE_SOURCE.to_string(), TRIPLE_TARGET.to_string()));
+        assert_eq!(result, LinkPermutation::new(TRIPLE_SOURCE.to_string(), TRIPLE_PREDICATE.to_string()));
 
         // The triple contains source, target
         let triple = Triple {
@@ -320,4 +573,267 @@ mod tests {
         let result = get_link_permutation_by(triple);
         assert_eq!(result, LinkPermutation::new(WILDCARD.to_string(), WILDCARD.to_string()));
     }
+
+    #[test]
+    fn triple_matches_works() {
+        let triple = Triple {
+            source: Some(TRIPLE_SOURCE.to_string()),
+            target: Some(TRIPLE_TARGET.to_string()),
+            predicate: Some(TRIPLE_PREDICATE.to_string()),
+        };
+
+        // Query elements which are exactly equal match
+        let query = Triple {
+            source: Some(TRIPLE_SOURCE.to_string()),
+            target: Some(TRIPLE_TARGET.to_string()),
+            predicate: None,
+        };
+        assert!(triple_matches(&query, &triple));
+
+        // Empty query matches everything
+        let query = Triple {
+            source: None,
+            target: None,
+            predicate: None,
+        };
+        assert!(triple_matches(&query, &triple));
+
+        // Query element which is only a prefix of the triple element does not match
+        let query = Triple {
+            source: Some(TRIPLE_SOURCE.to_string()),
+            target: Some("targ".to_string()),
+            predicate: None,
+        };
+        assert!(!triple_matches(&query, &triple));
+
+        // Query element does not match a missing triple element
+        let triple = Triple {
+            source: Some(TRIPLE_SOURCE.to_string()),
+            target: None,
+            predicate: Some(TRIPLE_PREDICATE.to_string()),
+        };
+        let query = Triple {
+            source: None,
+            target: Some(TRIPLE_TARGET.to_string()),
+            predicate: None,
+        };
+        assert!(!triple_matches(&query, &triple));
+    }
+
+    #[test]
+    fn pattern_matches_works() {
+        let triple = Triple {
+            source: Some(TRIPLE_SOURCE.to_string()),
+            target: Some(TRIPLE_TARGET.to_string()),
+            predicate: Some(TRIPLE_PREDICATE.to_string()),
+        };
+
+        // Empty pattern matches everything
+        let pattern = TriplePattern {
+            source: None,
+            predicate: None,
+            target_prefix: None,
+        };
+        assert!(pattern_matches(&pattern, &triple));
+
+        // Source & predicate must match exactly, target by prefix
+        let pattern = TriplePattern {
+            source: Some(TRIPLE_SOURCE.to_string()),
+            predicate: Some(TRIPLE_PREDICATE.to_string()),
+            target_prefix: Some("tar".to_string()),
+        };
+        assert!(pattern_matches(&pattern, &triple));
+
+        let pattern = TriplePattern {
+            source: Some("sour".to_string()),
+            predicate: None,
+            target_prefix: None,
+        };
+        assert!(!pattern_matches(&pattern, &triple));
+
+        let pattern = TriplePattern {
+            source: None,
+            predicate: None,
+            target_prefix: Some("other".to_string()),
+        };
+        assert!(!pattern_matches(&pattern, &triple));
+    }
+
+    fn did_key_for(key: &[u8; 32]) -> String {
+        let mut bytes = vec![0xed, 0x01];
+        bytes.extend_from_slice(key);
+        format!("did:key:z{}", bs58::encode(bytes).into_string())
+    }
+
+    #[test]
+    fn resolve_did_key_works() {
+        let key = [7u8; 32];
+        let did = did_key_for(&key);
+        assert_eq!(resolve_did_key(&did), Some(agent_pub_key_from_raw_32(&key)));
+
+        // Not a did:key, not base58 or not an ed25519 key
+        assert_eq!(resolve_did_key("did:web:example.com"), None);
+        assert_eq!(resolve_did_key("did:key:z0OIl"), None);
+        let mut secp256k1 = vec![0xe7, 0x01];
+        secp256k1.extend_from_slice(&[7u8; 33]);
+        assert_eq!(resolve_did_key(&format!("did:key:z{}", bs58::encode(secp256k1).into_string())), None);
+    }
+
+    #[test]
+    fn forged_proof_key_is_rejected() {
+        let author = did_key_for(&[1u8; 32]);
+        let forger = did_key_for(&[2u8; 32]);
+        assert!(proof_key_of(&author, &author));
+        assert!(proof_key_of(&author, &format!("{}#key-1", author)));
+
+        // A proof signed with the forger's own key can not claim to be made by the author
+        assert!(!proof_key_of(&author, &forger));
+        assert!(!proof_key_of(&author, &format!("{}#key-1", forger)));
+        assert!(!proof_key_of(&author, &format!("{}extra", author)));
+
+        let mut link = link_at(TRIPLE_TARGET, 0);
+        link.author = author;
+        link.proof.key = forger;
+        assert!(link.signing_key().is_err());
+    }
+
+    #[test]
+    fn ad4m_proof_verifies() {
+        use k256::ecdsa::signature::Signer;
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+        let signing_key = k256::ecdsa::SigningKey::from_bytes(&[1u8; 32]).unwrap();
+        let mut bytes = vec![0xe7, 0x01];
+        bytes.extend_from_slice(signing_key.verifying_key().to_encoded_point(true).as_bytes());
+        let author = format!("did:key:z{}", bs58::encode(bytes).into_string());
+
+        // AD4M signs JSON.stringify({data, timestamp}) with "" for the missing predicate
+        let mut link = link_at(TRIPLE_TARGET, 1641031200);
+        link.author = author.clone();
+        link.data.predicate = None;
+        let message = r#"{"data":{"source":"source","target":"target","predicate":""},"timestamp":"2022-01-01T10:00:00.000Z"}"#;
+        let signature: Secp256k1Signature = signing_key.sign(message.as_bytes());
+        link.proof = crate::inputs::ExpressionProof {
+            signature: hex::encode(signature.as_ref()),
+            key: format!("{}#primary", author),
+        };
+        assert!(link.verify_proof().unwrap());
+
+        // The signature does not cover other data
+        let mut tampered = link.clone();
+        tampered.data.target = Some(String::from("other"));
+        assert!(!tampered.verify_proof().unwrap());
+    }
+
+    #[test]
+    fn get_language_works() {
+        assert_eq!(get_language("literal://string:hello"), Some("literal"));
+        assert_eq!(get_language("QmHash123://expression"), Some("QmHash123"));
+        assert_eq!(get_language("no-language"), None);
+    }
+
+    #[test]
+    fn rules_permit_works() {
+        let rules = AccessRules::default();
+        assert!(rules_permit(&rules, "sioc://has_member"));
+
+        let rules = AccessRules {
+            allow: Some(vec!["sioc://has_member".to_string()]),
+            deny: vec![],
+        };
+        assert!(rules_permit(&rules, "sioc://has_member"));
+        assert!(!rules_permit(&rules, "sioc://has_reply"));
+
+        // Deny wins over allow
+        let rules = AccessRules {
+            allow: Some(vec!["sioc://has_member".to_string()]),
+            deny: vec!["sioc://has_member".to_string()],
+        };
+        assert!(!rules_permit(&rules, "sioc://has_member"));
+
+        let rules = AccessRules {
+            allow: None,
+            deny: vec!["sioc://has_reply".to_string()],
+        };
+        assert!(rules_permit(&rules, "sioc://has_member"));
+        assert!(!rules_permit(&rules, "sioc://has_reply"));
+    }
+
+    #[test]
+    fn within_skew_works() {
+        let reference = link_at(TRIPLE_TARGET, 1000).timestamp;
+        let skew = chrono::Duration::seconds(60);
+        assert!(within_skew(reference, reference, skew));
+        assert!(within_skew(link_at(TRIPLE_TARGET, 940).timestamp, reference, skew));
+        assert!(within_skew(link_at(TRIPLE_TARGET, 1060).timestamp, reference, skew));
+        assert!(!within_skew(link_at(TRIPLE_TARGET, 939).timestamp, reference, skew));
+        assert!(!within_skew(link_at(TRIPLE_TARGET, 1061).timestamp, reference, skew));
+    }
+
+    #[test]
+    fn filter_by_time_span_works() {
+        let links = vec![link_at("c", 30), link_at("a", 10), link_at("d", 40), link_at("b", 20)];
+        let time = |timestamp| DateTime::<Utc>::from_utc(chrono::NaiveDateTime::from_timestamp(timestamp, 0), Utc);
+
+        // No range given returns all links in order
+        let result = filter_by_time_span(links.clone(), None, Order::Ascending);
+        assert_eq!(targets(&result), vec!["a", "b", "c", "d"]);
+        let result = filter_by_time_span(links.clone(), None, Order::Descending);
+        assert_eq!(targets(&result), vec!["d", "c", "b", "a"]);
+
+        // Range is inclusive
+        let result = filter_by_time_span(links.clone(), Some((time(20), time(40))), Order::Ascending);
+        assert_eq!(targets(&result), vec!["b", "c", "d"]);
+
+        // Range arrangement does not affect the explicit order
+        let result = filter_by_time_span(links.clone(), Some((time(30), time(0))), Order::Ascending);
+        assert_eq!(targets(&result), vec!["a", "b", "c"]);
+        let result = filter_by_time_span(links.clone(), Some((time(0), time(30))), Order::Descending);
+        assert_eq!(targets(&result), vec!["c", "b", "a"]);
+
+        // Links with the same timestamp are ordered by their triple
+        let links = vec![link_at("y", 10), link_at("x", 10)];
+        let result = filter_by_time_span(links, None, Order::Ascending);
+        assert_eq!(targets(&result), vec!["x", "y"]);
+    }
+
+    #[test]
+    fn is_committed_form_of_works() {
+        let submitted = link_at("a", 10);
+        let mut committed = submitted.clone();
+        committed.index_mode = Some(crate::inputs::IndexMode::TimeIndex);
+        assert!(committed.is_committed_form_of(&submitted));
+
+        // Fields supplied by the caller have to match
+        let mut submitted_path = submitted.clone();
+        submitted_path.index_mode = Some(crate::inputs::IndexMode::Path);
+        assert!(!committed.is_committed_form_of(&submitted_path));
+        assert!(!committed.is_committed_form_of(&link_at("b", 10)));
+        assert!(!committed.is_committed_form_of(&link_at("a", 20)));
+
+        // A permissions version filled in by add_link does not have to be supplied
+        committed.permissions = Some(HeaderHash::from_raw_36(vec![0xdb; 36]));
+        assert!(committed.is_committed_form_of(&submitted));
+        let mut submitted_other = submitted.clone();
+        submitted_other.permissions = Some(HeaderHash::from_raw_36(vec![0xdc; 36]));
+        assert!(!committed.is_committed_form_of(&submitted_other));
+
+        // As does a DID binding
+        committed.did_binding = Some(HeaderHash::from_raw_36(vec![0xdd; 36]));
+        assert!(committed.is_committed_form_of(&submitted));
+    }
+
+    #[test]
+    fn resolve_order_works() {
+        let time = |timestamp| Some(DateTime::<Utc>::from_utc(chrono::NaiveDateTime::from_timestamp(timestamp, 0), Utc));
+
+        assert_eq!(resolve_order(None, None, None), Order::Ascending);
+        assert_eq!(resolve_order(time(0), time(10), None), Order::Ascending);
+        assert_eq!(resolve_order(time(10), time(0), None), Order::Descending);
+        assert_eq!(resolve_order(None, None, Some(Order::Descending)), Order::Descending);
+        assert_eq!(resolve_order(time(10), time(0), Some(Order::Ascending)), Order::Ascending);
+
+        assert_eq!(arrange_time_span(time(10).unwrap(), time(0).unwrap(), Order::Ascending), (time(0).unwrap(), time(10).unwrap()));
+        assert_eq!(arrange_time_span(time(0).unwrap(), time(10).unwrap(), Order::Descending), (time(10).unwrap(), time(0).unwrap()));
+    }
 }
@@ -1,4 +1,279 @@
-//use hdk::prelude::*;
+use hdk::prelude::*;
+
+use crate::errors::SocialContextError;
+use crate::utils::{decode_signature, is_entry_of, resolve_did_key, to_datetime, within_skew};
+use crate::{
+    DidBinding, LinkExpression, LinkRemoval, Permissions, ADMINS, ENFORCE_SPAM_LIMIT, MAX_CHUNK_INTERVAL, MAX_TIMESTAMP_SKEW,
+    VALIDATE_LINK_AUTHORS, VALIDATE_LINK_PROOFS,
+};
+
+/// Every header is validated through its StoreElement op, which carries the header & entry the rules below need
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    let element = match op {
+        Op::StoreElement { element } => element,
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+    match element.header() {
+        Header::Create(_) => validate_create_entry(&element),
+        Header::Update(_) => validate_update_entry(&element),
+        Header::Delete(delete) => validate_delete(delete),
+        Header::CreateLink(create_link) => validate_create_link(create_link),
+        Header::DeleteLink(delete_link) => validate_delete_link(delete_link),
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+fn validate_create_entry(element: &Element) -> ExternResult<ValidateCallbackResult> {
+    let header = element.header();
+    if is_entry_of(header, entry_def_index!(LinkExpression)?) {
+        validate_create_entry_link_data(element)
+    } else if is_entry_of(header, entry_def_index!(Permissions)?) {
+        validate_permissions_author(header.author())
+    } else if is_entry_of(header, entry_def_index!(LinkRemoval)?) {
+        validate_create_entry_link_removal(element)
+    } else if is_entry_of(header, entry_def_index!(DidBinding)?) {
+        validate_create_entry_did_binding(element)
+    } else {
+        Ok(ValidateCallbackResult::Valid)
+    }
+}
+
+/// LinkExpression(s) are bound to the Permissions version they were created under, so they can only be removed & re-added, never updated
+fn validate_update_entry(element: &Element) -> ExternResult<ValidateCallbackResult> {
+    let header = element.header();
+    if is_entry_of(header, entry_def_index!(LinkExpression)?) {
+        Ok(ValidateCallbackResult::Invalid(String::from("LinkExpression(s) cannot be updated")))
+    } else if is_entry_of(header, entry_def_index!(Permissions)?) {
+        validate_permissions_author(header.author())
+    } else {
+        Ok(ValidateCallbackResult::Valid)
+    }
+}
+
+fn validate_create_entry_link_data(element: &Element) -> ExternResult<ValidateCallbackResult> {
+    let link = match element.entry().to_app_option::<LinkExpression>()? {
+        Some(link) => link,
+        None => return Ok(ValidateCallbackResult::Invalid(String::from("Expected element to contain a LinkExpression"))),
+    };
+    //The header timestamp is set by the conductor committing the link so gives a deterministic reference for the client supplied timestamp
+    if let Some(skew) = *MAX_TIMESTAMP_SKEW {
+        if !within_skew(link.timestamp, to_datetime(element.header().timestamp()), skew) {
+            return Ok(ValidateCallbackResult::Invalid(
+                SocialContextError::InvalidTimestamp(link.timestamp, skew.num_seconds()).to_string(),
+            ));
+        };
+    };
+    if *VALIDATE_LINK_PROOFS {
+        match validate_link_proof(&link) {
+            ValidateCallbackResult::Valid => (),
+            invalid => return Ok(invalid),
+        }
+    };
+    if *VALIDATE_LINK_AUTHORS {
+        match validate_link_author(&link, element.header().author())? {
+            ValidateCallbackResult::Valid => (),
+            invalid => return Ok(invalid),
+        }
+    };
+    match validate_spam_limit(element.header())? {
+        ValidateCallbackResult::Valid => (),
+        invalid => return Ok(invalid),
+    };
+    //Validation can not know if a newer Permissions version exists, so only the rules of the referenced version are checked
+    match &link.permissions {
+        Some(permissions) => match get_permissions_version(permissions)? {
+            Some(permissions) => {
+                if let Err(err) = permissions.check_triple(&link.data) {
+                    return Ok(ValidateCallbackResult::Invalid(err.to_string()));
+                };
+            }
+            None => return Ok(ValidateCallbackResult::Invalid(String::from("LinkExpression permissions does not reference a Permissions entry"))),
+        },
+        None if !ADMINS.is_empty() => {
+            return Ok(ValidateCallbackResult::Invalid(String::from("LinkExpression must reference a Permissions version when admins are set")))
+        }
+        None => (),
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// A LinkRemoval can only be published by an agent who could delete the removed link, otherwise anyone could hide links from syncing agents
+fn validate_create_entry_link_removal(element: &Element) -> ExternResult<ValidateCallbackResult> {
+    let removal = match element.entry().to_app_option::<LinkRemoval>()? {
+        Some(removal) => removal,
+        None => return Ok(ValidateCallbackResult::Invalid(String::from("Expected element to contain a LinkRemoval"))),
+    };
+    let link_element = must_get_valid_element(removal.link_header.clone())?;
+    if link_element.header().entry_hash() != Some(&hash_entry(&removal.link)?) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("LinkRemoval link does not match the entry of its link header")));
+    };
+    let remover = element.header().author();
+    if link_element.header().author() != remover && !can_moderate(remover, &removal.link)? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of a link, admins or moderators can publish its removal",
+        )));
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Entries can only be deleted by their author, except LinkExpression(s) which can also be deleted by admins & moderators
+fn validate_delete(delete: &Delete) -> ExternResult<ValidateCallbackResult> {
+    let deleted = must_get_valid_element(delete.deletes_address.clone())?;
+    if deleted.header().author() == &delete.author {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    if is_entry_of(deleted.header(), entry_def_index!(LinkExpression)?) {
+        if let Some(link) = deleted.entry().to_app_option::<LinkExpression>()? {
+            if can_moderate(&delete.author, &link)? {
+                return Ok(ValidateCallbackResult::Valid);
+            };
+        };
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of a LinkExpression, admins or moderators can delete it",
+        )));
+    };
+    Ok(ValidateCallbackResult::Invalid(String::from("Only the author of an entry can delete it")))
+}
+
+/// Links can only be deleted by their author, except index links of a LinkExpression which can also be deleted by its admins & moderators
+fn validate_delete_link(delete_link: &DeleteLink) -> ExternResult<ValidateCallbackResult> {
+    let create_link = must_get_header(delete_link.link_add_address.clone())?;
+    let create_link = match create_link.header() {
+        Header::CreateLink(create_link) => create_link.to_owned(),
+        _ => return Ok(ValidateCallbackResult::Invalid(String::from("Deleted header is not a link"))),
+    };
+    if create_link.author == delete_link.author {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    if let Entry::App(bytes) = must_get_entry(create_link.target_address)?.as_content() {
+        if let Ok(link) = LinkExpression::try_from(bytes.to_owned().into_sb()) {
+            if can_moderate(&delete_link.author, &link)? {
+                return Ok(ValidateCallbackResult::Valid);
+            };
+        };
+    };
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Only the author of a link, admins or moderators can delete it",
+    )))
+}
+
+/// Check if an agent is an admin or a moderator in the Permissions version the LinkExpression was created under
+pub(crate) fn can_moderate(agent: &AgentPubKey, link: &LinkExpression) -> ExternResult<bool> {
+    if ADMINS.contains(agent) {
+        return Ok(true);
+    };
+    match &link.permissions {
+        Some(permissions) => Ok(get_permissions_version(permissions)?
+            .map_or(false, |permissions| permissions.moderators.contains(agent))),
+        None => Ok(false),
+    }
+}
+
+/// Only admins can link from the "permissions" anchor, so other agents can not make their entries look like the Permissions entry
+fn validate_create_link(create_link: &CreateLink) -> ExternResult<ValidateCallbackResult> {
+    let permissions_anchor = Path::from("permissions").path_entry_hash()?;
+    if create_link.base_address == permissions_anchor && !ADMINS.contains(&create_link.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("Only admins can link from the permissions anchor")));
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_permissions_author(agent: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+    if !ADMINS.contains(agent) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("Only admins can create or update permissions")));
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Resolve the Permissions version referenced by a LinkExpression.
+/// Depending on a specific header keeps validation deterministic however the Permissions entry is updated later
+pub(crate) fn get_permissions_version(permissions: &HeaderHash) -> ExternResult<Option<Permissions>> {
+    let element = must_get_valid_element(permissions.to_owned())?;
+    Ok(element.entry().to_app_option::<Permissions>().ok().flatten())
+}
+
+fn validate_create_entry_did_binding(element: &Element) -> ExternResult<ValidateCallbackResult> {
+    let binding = match element.entry().to_app_option::<DidBinding>()? {
+        Some(binding) => binding,
+        None => return Ok(ValidateCallbackResult::Invalid(String::from("Expected element to contain a DidBinding"))),
+    };
+    if &binding.agent != element.header().author() {
+        return Ok(ValidateCallbackResult::Invalid(String::from("Agents can only bind a DID to their own agent key")));
+    };
+    //The DID key must have signed the agent key, otherwise any agent could claim any DID
+    let did_key = match resolve_did_key(&binding.did) {
+        Some(did_key) => did_key,
+        None => return Ok(ValidateCallbackResult::Invalid(String::from("DidBinding DID must be an ed25519 did:key"))),
+    };
+    let signature = match decode_signature(&binding.signature) {
+        Ok(signature) => signature,
+        Err(err) => return Ok(ValidateCallbackResult::Invalid(String::from(err))),
+    };
+    if !verify_signature_raw(did_key, signature, binding.agent.get_raw_39().to_vec())? {
+        return Ok(ValidateCallbackResult::Invalid(String::from("DidBinding signature was not made over the agent key by the DID key")));
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Require that the LinkExpression references a valid DidBinding of its author DID to the agent committing the link.
+/// A referenced binding which can not be found yet is an unresolved dependency; a missing or mismatched binding is invalid
+fn validate_link_author(link: &LinkExpression, agent: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+    let binding_header = match &link.did_binding {
+        Some(binding_header) => binding_header,
+        None => return Ok(ValidateCallbackResult::Invalid(String::from("LinkExpression does not reference a DidBinding of its author"))),
+    };
+    let element = match must_get_valid_element(binding_header.to_owned()) {
+        Ok(element) => element,
+        Err(_) => return Ok(ValidateCallbackResult::UnresolvedDependencies(vec![binding_header.to_owned().into()])),
+    };
+    match element.entry().to_app_option::<DidBinding>().ok().flatten() {
+        Some(binding) if binding.did == link.author && &binding.agent == agent => Ok(ValidateCallbackResult::Valid),
+        _ => Ok(ValidateCallbackResult::Invalid(String::from("LinkExpression DidBinding does not bind its author to the committing agent"))),
+    }
+}
+
+/// Count the author's links committed within MAX_CHUNK_INTERVAL before this header, rejecting the link if there are more than ENFORCE_SPAM_LIMIT.
+/// Only depends on the chain before the header so every validator comes to the same result
+fn validate_spam_limit(header: &Header) -> ExternResult<ValidateCallbackResult> {
+    let window_start = to_datetime(header.timestamp()) - *MAX_CHUNK_INTERVAL;
+    let spam_limit = || {
+        ValidateCallbackResult::Invalid(format!(
+            "Agent has committed more than {} links within {} seconds",
+            *ENFORCE_SPAM_LIMIT,
+            MAX_CHUNK_INTERVAL.num_seconds()
+        ))
+    };
+
+    //Walk back the chain, stopping at the first header before the window
+    let link_def = entry_def_index!(LinkExpression)?;
+    let mut links = 1;
+    let mut prev_header = header.prev_header().cloned();
+    while let Some(prev_header_hash) = prev_header {
+        let signed_header = must_get_header(prev_header_hash)?;
+        let header = signed_header.header();
+        if to_datetime(header.timestamp()) <= window_start {
+            break;
+        };
+        if is_entry_of(header, link_def) {
+            links += 1;
+            if links > *ENFORCE_SPAM_LIMIT {
+                return Ok(spam_limit());
+            };
+        };
+        prev_header = header.prev_header().cloned();
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Reject LinkExpression(s) where the proof does not verify against the author, data & timestamp
+fn validate_link_proof(link: &LinkExpression) -> ValidateCallbackResult {
+    match link.verify_proof() {
+        Ok(true) => ValidateCallbackResult::Valid,
+        Ok(false) => ValidateCallbackResult::Invalid(String::from("LinkExpression proof signature does not match its key and data")),
+        Err(err) => ValidateCallbackResult::Invalid(err.to_string()),
+    }
+}
 
 // fn return_app_entry_type(entry_type: &EntryType) -> Option<AppEntryType> {
 //     match entry_type {
@@ -31,32 +31,32 @@ module.exports = (orchestrator) => {
         );
     
         //Getting links after add
-        const subj_pred_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_pred_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_pred_links.length, 1);
 
-        const subj_obj_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_obj_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_obj_links.length, 1);
 
-        const obj_pred_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: "object-full", predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const obj_pred_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: "object-full", predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(obj_pred_links.length, 1);
 
-        const subj_wild_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_wild_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_wild_links.length, 1);
 
-        const obj_wild_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const obj_wild_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(obj_wild_links.length, 1);
 
-        const pred_wild_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const pred_wild_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(pred_wild_links.length, 1);
 
-        const wild_wild_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const wild_wild_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(wild_wild_links.length, 1);
         
         //Remove links
@@ -64,32 +64,32 @@ module.exports = (orchestrator) => {
         await sleep(1000);
     
         //Getting links after remove
-        const subj_pred_links_after = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-        {source: "subject-full", target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_pred_links_after = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+        {source: "subject-full", target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_pred_links_after.length, 0);
 
-        const subj_obj_links_after = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_obj_links_after = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_obj_links_after.length, 0);
 
-        const obj_pred_links_after = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: "object-full", predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const obj_pred_links_after = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: "object-full", predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(obj_pred_links_after.length, 0);
 
-        const subj_wild_links_after = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_wild_links_after = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_wild_links_after.length, 0);
 
-        const obj_wild_links_after = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const obj_wild_links_after = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(obj_wild_links_after.length, 0);
 
-        const pred_wild_links_after = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const pred_wild_links_after = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(pred_wild_links_after.length, 0);
 
-        const wild_wild_links_after = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const wild_wild_links_after = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(wild_wild_links_after.length, 0);
     })
 }
\ No newline at end of file
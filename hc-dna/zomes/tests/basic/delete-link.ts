@@ -27,13 +27,13 @@ module.exports = (orchestrator) => {
         await alice_sc_happ.cells[0].call("social_context", "add_link", add_link_input);
     
         console.log("Getting links");
-        const subj_pred_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_pred_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_pred_links.length, 1);
 
         //There are no links for source/target and other permutations, so no need to check after remove_link.
-        const subj_obj_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_obj_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_obj_links.length, 0);
         
         console.log("Removing link");
@@ -41,8 +41,8 @@ module.exports = (orchestrator) => {
         await sleep(1000);
     
         console.log("Getting links");
-        const subj_links_pd = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-        {source: "subject-full", target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_links_pd = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+        {source: "subject-full", target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_links_pd.length, 0);
     })
 }
\ No newline at end of file
@@ -35,7 +35,7 @@ module.exports = (orchestrator) => {
         }
         
         //Get links on subject; expect back nothing
-        const subj_links2 = await alice_sc_happ.cells[0].call(
+        const subj_links2 = (await alice_sc_happ.cells[0].call(
             "social_context",
             "get_links", 
             {
@@ -46,12 +46,12 @@ module.exports = (orchestrator) => {
                 until: new Date().toISOString(),
                 limit: 10
             }
-        );
+        )).links;
         t.deepEqual(subj_links2.length, 0);
         console.log("INT-TEST: subject links", subj_links2);
     
         //Get links on subject & object; expect back nothing 
-        const subj_obj_links2 = await alice_sc_happ.cells[0].call(
+        const subj_obj_links2 = (await alice_sc_happ.cells[0].call(
             "social_context",
             "get_links", 
             {
@@ -62,12 +62,12 @@ module.exports = (orchestrator) => {
                 until: new Date().toISOString(),
                 limit: 10
             }
-        );
+        )).links;
         t.deepEqual(subj_obj_links2.length, 0);
         console.log("INT-TEST: subject object links", subj_obj_links2);
     
         //Get links on object; expect back nothing
-        const object_links2 = await alice_sc_happ.cells[0].call(
+        const object_links2 = (await alice_sc_happ.cells[0].call(
             "social_context",
             "get_links", 
             {
@@ -78,12 +78,12 @@ module.exports = (orchestrator) => {
                 until: new Date().toISOString(),
                 limit: 10
             }
-        );
+        )).links;
         t.deepEqual(object_links2.length, 0);
         console.log("INT-TEST: object links", object_links2);
     
         //Get links on object & predicate; expect back nothing
-        const object_pred_links2 = await alice_sc_happ.cells[0].call(
+        const object_pred_links2 = (await alice_sc_happ.cells[0].call(
             "social_context",
             "get_links", 
             {
@@ -94,12 +94,12 @@ module.exports = (orchestrator) => {
                 until: new Date().toISOString(),
                 limit: 10
             }
-        );
+        )).links;
         t.deepEqual(object_pred_links2.length, 0);
         console.log("INT-TEST: object predicate links", object_pred_links2)
     
         //Get links on predicate; expect back nothing
-        const pred_links2 = await alice_sc_happ.cells[0].call(
+        const pred_links2 = (await alice_sc_happ.cells[0].call(
             "social_context",
             "get_links", 
             {
@@ -110,7 +110,7 @@ module.exports = (orchestrator) => {
                 until: new Date().toISOString(),
                 limit: 10
             }
-        );
+        )).links;
         t.deepEqual(pred_links2.length, 0);
         console.log("INT-TEST: predicate links", pred_links2)
     })
@@ -23,38 +23,38 @@ module.exports = (orchestrator) => {
         })
     
         //Get links on subject
-        const subj_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-3", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-3", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_links3.length, 0);
         console.log("INT-TEST: subject links", subj_links3);
     
         //Get links on subject & object
-        const subj_obj_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-3", target: "object-3", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_obj_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-3", target: "object-3", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_obj_links3.length, 0);
         console.log("INT-TEST: subject object links", subj_obj_links3);
     
         //Get links on subject and predicate
-        const subj_pred_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-3", target: null, predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_pred_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-3", target: null, predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_pred_links3.length, 1);
         console.log("INT-TEST: subject links", subj_links3);
     
         //Get links on object
-        const object_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: "object-3", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const object_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: "object-3", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(object_links3.length, 0);
         console.log("INT-TEST: object links", object_links3);
     
         //Get links on object & predicate
-        const object_pred_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: "object-3", predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const object_pred_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: "object-3", predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(object_pred_links3.length, 0);
         console.log("INT-TEST: object predicate links", object_pred_links3)
     
         //Get links on predicate
-        const pred_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: null, predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const pred_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: null, predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(pred_links3.length, 0);
         console.log("INT-TEST: predicate links", pred_links3)
         t.pass()
@@ -0,0 +1,35 @@
+import { localConductorConfig, installation, sleep } from '../common'
+
+function linkInput(source: string, target: string, predicate: string | null, indexType = "FullWithWildCard") {
+    return {
+        linkExpression: {
+            data: {source, target, predicate},
+            author: "test1", timestamp: new Date().toISOString(), proof: {signature: "sig", key: "key"}
+        },
+        indexStrategy: {
+            type: indexType
+        },
+    };
+}
+
+module.exports = (orchestrator) => {
+	orchestrator.registerScenario("batch add links", async (s, t) => {
+        const [alice] = await s.players([localConductorConfig])
+        const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+
+        const inputs = [
+            linkInput("subject-batch-add", "object-batch-add-1", "predicate-batch-add"),
+            linkInput("subject-batch-add", "object-batch-add-2", "predicate-batch-add"),
+            //Simple index strategy requires a predicate, so only this link fails
+            linkInput("subject-batch-add", "object-batch-add-3", null, "Simple"),
+        ];
+        const results = await alice_sc_happ.cells[0].call("social_context", "add_links", inputs);
+        t.deepEqual(results.map((result) => result.success), [true, true, false]);
+        t.ok(results[2].error);
+        await sleep(1000);
+
+        //The failed link was not committed
+        const links = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject-batch-add"})).links
+        t.deepEqual(links.map((link) => link.data.target).sort(), ["object-batch-add-1", "object-batch-add-2"]);
+    })
+}
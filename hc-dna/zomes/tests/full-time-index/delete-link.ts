@@ -24,16 +24,44 @@ module.exports = (orchestrator) => {
         await alice_sc_happ.cells[0].call("social_context", "add_link", add_link_input);
         
         //Get links on subject
-        const subj_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_links.length, 1);
     
         await alice_sc_happ.cells[0].call("social_context", "remove_link", add_link_input.linkExpression);
         await sleep(1000);
   
         //Get links on subject
-        const subj_links_pd = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_links_pd = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_links_pd.length, 0);
     })
+
+	orchestrator.registerScenario("Link delete by another agent is rejected", async (s, t) => {
+        const [alice, bob] = await s.players([localConductorConfig, localConductorConfig])
+        const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+        const [[bob_sc_happ]] = await bob.installAgentsHapps(installation)
+        await s.shareAllNodes([alice, bob])
+
+        let add_link_input = {
+            linkExpression: {
+                data: {source: "subject-other-delete", target: "object-other-delete", predicate: "predicate-other-delete"},
+                author: "test1", timestamp: new Date().toISOString(), proof: {signature: "sig", key: "key"}
+            },
+            indexStrategy: {
+                type: "FullWithWildCard"
+            },
+        };
+        await alice_sc_happ.cells[0].call("social_context", "add_link", add_link_input);
+        await sleep(2000);
+
+        //Bob is neither the author, an admin nor a moderator of alice's link
+        const rejected = await bob_sc_happ.cells[0].call("social_context", "remove_link", add_link_input.linkExpression)
+            .then(() => false, () => true);
+        t.ok(rejected);
+        await sleep(1000);
+
+        const links = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject-other-delete"})).links
+        t.deepEqual(links.length, 1);
+    })
 }
\ No newline at end of file
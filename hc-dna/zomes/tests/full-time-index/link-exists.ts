@@ -0,0 +1,49 @@
+import { localConductorConfig, installation, sleep } from '../common'
+
+function linkInput(source: string, target: string, predicate: string | null) {
+    return {
+        linkExpression: {
+            data: {source, target, predicate},
+            author: "test1", timestamp: new Date().toISOString(), proof: {signature: "sig", key: "key"}
+        },
+        indexStrategy: {
+            type: "FullWithWildCard"
+        },
+    };
+}
+
+module.exports = (orchestrator) => {
+	orchestrator.registerScenario("full triple get_links & link exists", async (s, t) => {
+        const [alice] = await s.players([localConductorConfig])
+        const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+
+        //Targets share a prefix and the source + predicate index, so a full triple query has to skip past them
+        for (const target of ["object-exists-1", "object-exists-10", "object-exists-11", "object-exists-2"]) {
+            await alice_sc_happ.cells[0].call("social_context", "add_link", linkInput("subject-exists", target, "predicate-exists"));
+        }
+        await sleep(1000);
+
+        //Full triple query only returns the exact match, even when limited
+        const exact = (await alice_sc_happ.cells[0].call("social_context", "get_links",
+            {source: "subject-exists", target: "object-exists-2", predicate: "predicate-exists", limit: 1})).links
+        t.deepEqual(exact.length, 1);
+        t.deepEqual(exact[0].data.target, "object-exists-2");
+
+        const exists = await alice_sc_happ.cells[0].call("social_context", "link_exists",
+            {source: "subject-exists", target: "object-exists-1", predicate: "predicate-exists"});
+        t.deepEqual(exists.exists, true);
+        t.deepEqual(exists.linkHashes.length, 1);
+
+        const notExists = await alice_sc_happ.cells[0].call("social_context", "link_exists",
+            {source: "subject-exists", target: "object-exists-1", predicate: "predicate-other"});
+        t.deepEqual(notExists.exists, false);
+        t.deepEqual(notExists.linkHashes.length, 0);
+
+        //Removed links no longer exist
+        await alice_sc_happ.cells[0].call("social_context", "remove_link", exact[0]);
+        await sleep(1000);
+        const removed = await alice_sc_happ.cells[0].call("social_context", "link_exists",
+            {source: "subject-exists", target: "object-exists-2", predicate: "predicate-exists"});
+        t.deepEqual(removed.exists, false);
+    })
+}
@@ -0,0 +1,71 @@
+import { localConductorConfig, installation, sleep } from '../common'
+
+module.exports = (orchestrator) => {
+	orchestrator.registerScenario("get links since", async (s, t) => {
+        const [alice] = await s.players([localConductorConfig])
+        const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+
+        const since = new Date().toISOString();
+        await sleep(1000);
+
+        let add_link_input = {
+            linkExpression: {
+                data: {source: "subject-since", target: "object-since", predicate: "predicate-since"},
+                author: "test1", timestamp: new Date().toISOString(), proof: {signature: "sig", key: "key"}
+            },
+            indexStrategy: {
+                type: "FullWithWildCard"
+            },
+        };
+        await alice_sc_happ.cells[0].call("social_context", "add_link", add_link_input);
+
+        //Link added after the sync timestamp is returned as an addition
+        const firstSync = await alice_sc_happ.cells[0].call("social_context", "get_links_since", {timestamp: since});
+        t.deepEqual(firstSync.added.length, 1);
+        t.deepEqual(firstSync.removed.length, 0);
+
+        await sleep(1000);
+        await alice_sc_happ.cells[0].call("social_context", "remove_link", add_link_input.linkExpression);
+        await sleep(1000);
+
+        //Syncing from the returned cursor only returns the removal
+        const secondSync = await alice_sc_happ.cells[0].call("social_context", "get_links_since", {cursor: firstSync.cursor});
+        t.deepEqual(secondSync.added.length, 0);
+        t.deepEqual(secondSync.removed.length, 1);
+        t.deepEqual(secondSync.removed[0].data.source, "subject-since");
+        t.deepEqual(secondSync.hasMore, false);
+    })
+
+	orchestrator.registerScenario("get links since with a limit", async (s, t) => {
+        const [alice] = await s.players([localConductorConfig])
+        const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+
+        const since = new Date().toISOString();
+        await sleep(1000);
+
+        //Links are synced whatever index & strategy they were added with
+        for (const [target, indexMode] of [["object-1", "Path"], ["object-2", "TimeIndex"], ["object-3", "Path"]]) {
+            await alice_sc_happ.cells[0].call("social_context", "add_link", {
+                linkExpression: {
+                    data: {source: "subject-since-limit", target, predicate: null},
+                    author: "test1", timestamp: new Date().toISOString(), proof: {signature: "sig", key: "key"}
+                },
+                indexStrategy: {
+                    type: "Full"
+                },
+                indexMode: {
+                    type: indexMode
+                },
+            });
+            await sleep(100);
+        }
+
+        const firstPage = await alice_sc_happ.cells[0].call("social_context", "get_links_since", {timestamp: since, limit: 2});
+        t.deepEqual(firstPage.added.map((link) => link.data.target), ["object-1", "object-2"]);
+        t.deepEqual(firstPage.hasMore, true);
+
+        const secondPage = await alice_sc_happ.cells[0].call("social_context", "get_links_since", {cursor: firstPage.cursor, limit: 2});
+        t.deepEqual(secondPage.added.map((link) => link.data.target), ["object-3"]);
+        t.deepEqual(secondPage.hasMore, false);
+    })
+}
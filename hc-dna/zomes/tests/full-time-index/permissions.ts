@@ -0,0 +1,68 @@
+import { localConductorConfig, installation, sleep } from '../common'
+import path from 'path'
+
+//Properties of dna_time_index.yaml, with the given agent as the only admin
+function propertiesWithAdmin(admin: Buffer) {
+    return {
+        "enforce_spam_limit": 1000,
+        "max_chunk_interval": 43200000,
+        "active_agent_duration_s": 300,
+        "enable_signals": false,
+        "enable_time_index": true,
+        "validate_link_proofs": false,
+        "validate_link_authors": false,
+        //Agent keys are encoded as "u" prefixed url safe base64, like AgentPubKeyB64
+        "admins": ["u" + Buffer.from(admin).toString("base64").replace(/\+/g, "-").replace(/\//g, "_")],
+        "max_timestamp_skew_s": 300,
+        "sync_overlap_s": 300,
+    };
+}
+
+function linkInput(predicate: string) {
+    return {
+        linkExpression: {
+            data: {source: "subject-permissions", target: "object-permissions", predicate},
+            author: "test1", timestamp: new Date().toISOString(), proof: {signature: "sig", key: "key"}
+        },
+        indexStrategy: {
+            type: "FullWithWildCard"
+        },
+    };
+}
+
+module.exports = (orchestrator) => {
+	orchestrator.registerScenario("update permissions is rejected for non admins", async (s, t) => {
+        const [alice] = await s.players([localConductorConfig])
+        const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+
+        //dna_time_index.yaml has no admins, so nobody can set the rules of the social context
+        const rejected = await alice_sc_happ.cells[0].call("social_context", "update_permissions",
+            {moderators: [], predicates: {allow: null, deny: ["predicate-denied"]}, languages: {allow: null, deny: []}})
+            .then(() => false, () => true);
+        t.ok(rejected);
+
+        const permissions = await alice_sc_happ.cells[0].call("social_context", "get_permissions", null);
+        t.deepEqual(permissions.permissions, null);
+    })
+
+	orchestrator.registerScenario("links with a denied predicate are rejected", async (s, t) => {
+        const [alice] = await s.players([localConductorConfig])
+        const adminKey = await alice.adminWs().generateAgentPubKey()
+        const dnaHash = await alice.registerDna({path: path.join("../../workdir/social-context.dna")}, undefined, propertiesWithAdmin(adminKey))
+        const alice_sc_happ = await alice.installHapp([dnaHash], adminKey)
+
+        //The admin creates the default permissions in init, then denies a predicate
+        await alice_sc_happ.cells[0].call("social_context", "update_permissions",
+            {moderators: [], predicates: {allow: null, deny: ["predicate-denied"]}, languages: {allow: null, deny: []}})
+        await sleep(1000);
+
+        const rejected = await alice_sc_happ.cells[0].call("social_context", "add_link", linkInput("predicate-denied"))
+            .then(() => false, () => true);
+        t.ok(rejected);
+        await alice_sc_happ.cells[0].call("social_context", "add_link", linkInput("predicate-allowed"));
+        await sleep(1000);
+
+        const links = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject-permissions"})).links
+        t.deepEqual(links.map((link) => link.data.predicate), ["predicate-allowed"]);
+    })
+}
@@ -0,0 +1,38 @@
+import { localConductorConfig, installation, sleep } from '../common'
+
+function linkInput(source: string, target: string, predicate: string) {
+    return {
+        linkExpression: {
+            data: {source, target, predicate},
+            author: "test1", timestamp: new Date().toISOString(), proof: {signature: "sig", key: "key"}
+        },
+        indexStrategy: {
+            type: "FullWithWildCard"
+        },
+    };
+}
+
+module.exports = (orchestrator) => {
+	orchestrator.registerScenario("batch remove links", async (s, t) => {
+        const [alice] = await s.players([localConductorConfig])
+        const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+
+        for (const target of ["object-batch-remove-1", "object-batch-remove-2", "object-batch-remove-3", "object-batch-remove-4"]) {
+            await alice_sc_happ.cells[0].call("social_context", "add_link", linkInput("subject-batch-remove", target, "predicate-batch-remove"));
+        }
+        await sleep(1000);
+        const links = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject-batch-remove"})).links
+        t.deepEqual(links.length, 4);
+
+        //Remove two links by expression & one by hash
+        const byExpression = links.filter((link) => link.data.target == "object-batch-remove-2" || link.data.target == "object-batch-remove-3");
+        await alice_sc_happ.cells[0].call("social_context", "remove_links", byExpression);
+        const byHash = await alice_sc_happ.cells[0].call("social_context", "link_exists",
+            {source: "subject-batch-remove", target: "object-batch-remove-1", predicate: "predicate-batch-remove"});
+        await alice_sc_happ.cells[0].call("social_context", "remove_links_by_hash", byHash.linkHashes);
+        await sleep(1000);
+
+        const remaining = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject-batch-remove"})).links
+        t.deepEqual(remaining.map((link) => link.data.target), ["object-batch-remove-4"]);
+    })
+}
@@ -26,28 +26,28 @@ module.exports = (orchestrator) => {
         })
     
         //Get links on subject; expect back object & predicate
-        const subj_links2 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source, target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_links2 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source, target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_links2.length, 1);
     
         //Get links on subject & object; expect back link 
-        const subj_obj_links2 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source, target, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_obj_links2 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source, target, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_obj_links2.length, 1);
     
         //Get links on object; expect back subject and predicate
-        const object_links2 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const object_links2 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(object_links2.length, 1);
     
         //Get links on object & predicate; expect back none
-        const object_pred_links2 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target, predicate: "predicate-2", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const object_pred_links2 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target, predicate: "predicate-2", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(object_pred_links2.length, 0);
     
         //Get links on predicate; expect back none
-        const pred_links2 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: null, predicate: "predicate-2", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const pred_links2 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: null, predicate: "predicate-2", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(pred_links2.length, 0);
     })
 }
\ No newline at end of file
@@ -25,28 +25,28 @@ module.exports = (orchestrator) => {
         })
     
         //Get links on subject
-        const subj_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-3", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-3", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_links3.length, 1);
     
         //Get links on subject & object
-        const subj_obj_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: "subject-3", target: "object-3", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const subj_obj_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: "subject-3", target: "object-3", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(subj_obj_links3.length, 0);
     
         //Get links on object
-        const object_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: "object-3", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const object_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: "object-3", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(object_links3.length, 0);
     
         //Get links on object & predicate
-        const object_pred_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: "object-3", predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const object_pred_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: "object-3", predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(object_pred_links3.length, 0);
     
         //Get links on predicate
-        const pred_links3 = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {source: null, target: null, predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+        const pred_links3 = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {source: null, target: null, predicate: "predicate-3", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
         t.deepEqual(pred_links3.length, 1);
     })
 }
\ No newline at end of file
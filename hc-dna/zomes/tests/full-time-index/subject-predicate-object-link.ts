@@ -25,38 +25,38 @@ module.exports = (orchestrator) => {
         })
 	
 		//Get links on subject; expect back object & predicate
-		const subj_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-		  {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+		const subj_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+		  {source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
 		t.deepEqual(subj_links.length, 1);
 
 		//Get links on subject; expect back object & predicate
-		const subj_pred_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-			{source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+		const subj_pred_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+			{source: "subject-full", target: null, predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
 		t.deepEqual(subj_pred_links.length, 1);
 
 		//Get links on subject
-		const incorrect_subj_pred_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-			{source: "object-full", target: null, predicate: "predicate-full"})
+		const incorrect_subj_pred_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+			{source: "object-full", target: null, predicate: "predicate-full"})).links
 		t.deepEqual(incorrect_subj_pred_links.length, 0);
 
 		//Get links on subject & object; expect back predicate 
-		const subj_obj_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-		  {source: "subject-full", target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+		const subj_obj_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+		  {source: "subject-full", target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
 		t.deepEqual(subj_obj_links.length, 1);
 	
 		//Get links on object; expect back subject and predicate
-		const object_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-		  {source: null, target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+		const object_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+		  {source: null, target: "object-full", predicate: null, from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
 		t.deepEqual(object_links.length, 1);
 	
 		//Get links on object & predicate; expect back subject
-		const object_pred_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-		  {source: null, target: "object-full", predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+		const object_pred_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+		  {source: null, target: "object-full", predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
 		t.deepEqual(object_pred_links.length, 1);
 	
 		//Get links on predicate; expect back subject and object
-		const pred_links = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-		  {source: null, target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})
+		const pred_links = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+		  {source: null, target: null, predicate: "predicate-full", from: date.toISOString(), until: new Date().toISOString(), limit: 10})).links
 		t.deepEqual(pred_links.length, 1);
 	})
 }
\ No newline at end of file
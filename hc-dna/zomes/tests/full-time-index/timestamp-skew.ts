@@ -0,0 +1,33 @@
+import { localConductorConfig, installation, sleep } from '../common'
+
+function linkAt(target: string, timestamp: Date) {
+    return {
+        linkExpression: {
+            data: {source: "subject-skew", target, predicate: "predicate-skew"},
+            author: "test1", timestamp: timestamp.toISOString(), proof: {signature: "sig", key: "key"}
+        },
+        indexStrategy: {
+            type: "FullWithWildCard"
+        },
+    };
+}
+
+module.exports = (orchestrator) => {
+	orchestrator.registerScenario("link timestamps outside the skew window are rejected", async (s, t) => {
+        const [alice] = await s.players([localConductorConfig])
+        const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+
+        //dna_time_index.yaml allows a max_timestamp_skew_s of 300 seconds
+        var hourAgo = new Date();
+        hourAgo.setTime(hourAgo.getTime() - 60*60*1000);
+        const rejected = await alice_sc_happ.cells[0].call("social_context", "add_link", linkAt("object-skew-old", hourAgo))
+            .then(() => false, () => true);
+        t.ok(rejected);
+
+        await alice_sc_happ.cells[0].call("social_context", "add_link", linkAt("object-skew-now", new Date()));
+        await sleep(1000);
+
+        const links = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject-skew"})).links
+        t.deepEqual(links.map((link) => link.data.target), ["object-skew-now"]);
+    })
+}
@@ -19,31 +19,31 @@ module.exports = (orchestrator) => {
         await alice_sc_happ.cells[0].call("social_context", "add_link", add_link_input);
         
         //Get links on nothing; wildcard
-        const wildcardLinks = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {})
+        const wildcardLinks = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {})).links
         t.deepEqual(wildcardLinks.length, 1);
 
         //Get links on source only
-        const wildcardLinksSource = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-            {source: "subject-full"})
+        const wildcardLinksSource = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+            {source: "subject-full"})).links
         t.deepEqual(wildcardLinksSource.length, 1);
 
         //Get links on target only
-        const wildcardLinksTarget = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-            {target: "object-full"})
+        const wildcardLinksTarget = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+            {target: "object-full"})).links
         t.deepEqual(wildcardLinksTarget.length, 1);
 
         //Get links on predicate only
-        const wildcardLinksPredicate = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-            {predicate: "predicate-full"})
+        const wildcardLinksPredicate = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+            {predicate: "predicate-full"})).links
         t.deepEqual(wildcardLinksPredicate.length, 1);
         
         await alice_sc_happ.cells[0].call("social_context", "remove_link", add_link_input.linkExpression);
         await sleep(1000);
   
         //Get links on subject
-        const deletedWildcardLinks = await alice_sc_happ.cells[0].call("social_context", "get_links", 
-          {})
+        const deletedWildcardLinks = (await alice_sc_happ.cells[0].call("social_context", "get_links", 
+          {})).links
         t.deepEqual(deletedWildcardLinks.length, 0);
     })
 }
\ No newline at end of file
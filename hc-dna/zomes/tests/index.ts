@@ -22,3 +22,27 @@ orchestrator.run()
 orchestrator = new Orchestrator()
 require('./full-time-index/wildcard-test')(orchestrator)
 orchestrator.run()
+
+orchestrator = new Orchestrator()
+require('./full-time-index/links-since')(orchestrator)
+orchestrator.run()
+
+orchestrator = new Orchestrator()
+require('./full-time-index/link-exists')(orchestrator)
+orchestrator.run()
+
+orchestrator = new Orchestrator()
+require('./full-time-index/add-links')(orchestrator)
+orchestrator.run()
+
+orchestrator = new Orchestrator()
+require('./full-time-index/remove-links')(orchestrator)
+orchestrator.run()
+
+orchestrator = new Orchestrator()
+require('./full-time-index/timestamp-skew')(orchestrator)
+orchestrator.run()
+
+orchestrator = new Orchestrator()
+require('./full-time-index/permissions')(orchestrator)
+orchestrator.run()
@@ -23,7 +23,7 @@ module.exports = (orchestrator) => {
             )
         }
         //Get all 35 messages and check that it works correctly
-        const allLinks = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: unixDate, untilDate: now.toISOString(), limit: 35})
+        const allLinks = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: unixDate, untilDate: now.toISOString(), limit: 35})).links
         console.log(allLinks);
         t.deepEqual(allLinks.length, 35);
         let last = undefined;
@@ -37,7 +37,7 @@ module.exports = (orchestrator) => {
         }
 
         //Get first page, should be from unix timestamp -> now with limit of 10 and then use last result to get the next page
-        const firstPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: unixDate, untilDate: now.toISOString(), limit: 10})
+        const firstPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: unixDate, untilDate: now.toISOString(), limit: 10})).links
         console.log(firstPage)
         t.deepEqual(firstPage.length, 10);
         last = undefined;
@@ -50,7 +50,7 @@ module.exports = (orchestrator) => {
             last = firstPage[step];
         }
 
-        const secondPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: firstPage[firstPage.length -1].timestamp, untilDate: now.toISOString(), limit: 10})
+        const secondPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: firstPage[firstPage.length -1].timestamp, untilDate: now.toISOString(), limit: 10})).links
         console.log(secondPage, firstPage[firstPage.length -1].timestamp);
         t.deepEqual(secondPage.length, 10);
         last = undefined;
@@ -63,7 +63,7 @@ module.exports = (orchestrator) => {
             last = secondPage[step];
         }
 
-        const thirdPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: secondPage[secondPage.length -1].timestamp, untilDate: now.toISOString(), limit: 10})
+        const thirdPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: secondPage[secondPage.length -1].timestamp, untilDate: now.toISOString(), limit: 10})).links
         console.log(thirdPage);
         t.deepEqual(thirdPage.length, 10);
         last = undefined;
@@ -76,7 +76,7 @@ module.exports = (orchestrator) => {
             last = thirdPage[step];
         }
 
-        const fourthPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: thirdPage[thirdPage.length -1].timestamp, untilDate: now.toISOString(), limit: 8})
+        const fourthPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: thirdPage[thirdPage.length -1].timestamp, untilDate: now.toISOString(), limit: 8})).links
         console.log(fourthPage);
         t.deepEqual(fourthPage.length, 8);
         last = undefined;
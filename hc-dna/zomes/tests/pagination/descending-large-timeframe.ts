@@ -23,7 +23,7 @@ module.exports = (orchestrator) => {
             )
         }
         //Get all 40 messages and check that it works correctly
-        const allLinks = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 50})
+        const allLinks = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 50})).links
         console.log(allLinks);
         t.deepEqual(allLinks.length, 40);
         let last = undefined;
@@ -37,7 +37,7 @@ module.exports = (orchestrator) => {
         }
 
         //Get first page, should be from now -> unix timestamp with limit of 10 and then use last result to get the next page
-        const firstPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 10})
+        const firstPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 10})).links
         console.log(firstPage)
         t.deepEqual(firstPage.length, 10);
         last = undefined;
@@ -50,7 +50,7 @@ module.exports = (orchestrator) => {
             last = firstPage[step];
         }
 
-        const secondPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: firstPage[firstPage.length -1].timestamp, untilDate: unixDate, limit: 10})
+        const secondPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: firstPage[firstPage.length -1].timestamp, untilDate: unixDate, limit: 10})).links
         console.log(secondPage);
         t.deepEqual(secondPage.length, 10);
         last = undefined;
@@ -63,7 +63,7 @@ module.exports = (orchestrator) => {
             last = secondPage[step];
         }
 
-        const thirdPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: secondPage[secondPage.length -1].timestamp, untilDate: unixDate, limit: 10})
+        const thirdPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: secondPage[secondPage.length -1].timestamp, untilDate: unixDate, limit: 10})).links
         console.log(thirdPage);
         t.deepEqual(thirdPage.length, 10);
         last = undefined;
@@ -76,7 +76,7 @@ module.exports = (orchestrator) => {
             last = thirdPage[step];
         }
 
-        const fourthPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: thirdPage[thirdPage.length -1].timestamp, untilDate: unixDate, limit: 10})
+        const fourthPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: thirdPage[thirdPage.length -1].timestamp, untilDate: unixDate, limit: 10})).links
         console.log(fourthPage);
         t.deepEqual(fourthPage.length, 10);
         last = undefined;
@@ -89,7 +89,7 @@ module.exports = (orchestrator) => {
             last = fourthPage[step];
         }
 
-        const fifthPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: fourthPage[fourthPage.length -1].timestamp, untilDate: unixDate, limit: 10})
+        const fifthPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: fourthPage[fourthPage.length -1].timestamp, untilDate: unixDate, limit: 10})).links
         console.log(fifthPage);
         t.deepEqual(fifthPage.length, 4);
         last = undefined;
@@ -23,7 +23,7 @@ module.exports = (orchestrator) => {
             )
         }
         //Get all 35 messages and check that it works correctly
-        const allLinks = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 35})
+        const allLinks = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 35})).links
         t.deepEqual(allLinks.length, 35);
         let last = undefined;
         for (let step = 0; step < allLinks.length; step ++) {
@@ -36,7 +36,7 @@ module.exports = (orchestrator) => {
         }
 
         //Get first page, should be from now -> unix timestamp with limit of 10 and then use last result to get the next page
-        const firstPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 10})
+        const firstPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 10})).links
         console.log(firstPage)
         t.deepEqual(firstPage.length, 10);
         last = undefined;
@@ -49,7 +49,7 @@ module.exports = (orchestrator) => {
             last = firstPage[step];
         }
 
-        const secondPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: firstPage[firstPage.length -1].timestamp, untilDate: unixDate, limit: 10})
+        const secondPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: firstPage[firstPage.length -1].timestamp, untilDate: unixDate, limit: 10})).links
         console.log(secondPage);
         t.deepEqual(secondPage.length, 10);
         last = undefined;
@@ -62,7 +62,7 @@ module.exports = (orchestrator) => {
             last = secondPage[step];
         }
 
-        const thirdPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: secondPage[secondPage.length -1].timestamp, untilDate: unixDate, limit: 10})
+        const thirdPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: secondPage[secondPage.length -1].timestamp, untilDate: unixDate, limit: 10})).links
         console.log(thirdPage);
         t.deepEqual(thirdPage.length, 10);
         last = undefined;
@@ -75,7 +75,7 @@ module.exports = (orchestrator) => {
             last = thirdPage[step];
         }
 
-        const fourthPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: thirdPage[thirdPage.length -1].timestamp, untilDate: unixDate, limit: 8})
+        const fourthPage = (await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: thirdPage[thirdPage.length -1].timestamp, untilDate: unixDate, limit: 8})).links
         console.log(fourthPage);
         t.deepEqual(fourthPage.length, 8);
         last = undefined;
@@ -88,6 +88,28 @@ module.exports = (orchestrator) => {
             last = fourthPage[step];
         }
 
+        //Page through the same links using the cursor returned with each page, pages should not overlap
+        const firstCursorPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 10})
+        t.deepEqual(firstCursorPage.links.length, 10);
+        t.notEqual(firstCursorPage.cursor, null);
+
+        const secondCursorPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 10, cursor: firstCursorPage.cursor})
+        t.deepEqual(secondCursorPage.links.length, 10);
+        for (let step = 0; step < secondCursorPage.links.length; step ++) {
+            t.deepEqual(secondCursorPage.links[step].data.target, `target-${step+10}`)
+        }
+
+        const lastCursorPage = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", fromDate: now.toISOString(), untilDate: unixDate, limit: 20, cursor: secondCursorPage.cursor})
+        t.deepEqual(lastCursorPage.links.length, 15);
+        t.deepEqual(lastCursorPage.cursor, null);
+
+        //Get the latest links using an explicit order instead of arranging fromDate & untilDate
+        const latestLinks = await alice_sc_happ.cells[0].call("social_context", "get_links", {source: "subject", target: null, predicate: "predicate", order: {type: "Descending"}, limit: 5})
+        t.deepEqual(latestLinks.links.length, 5);
+        for (let step = 0; step < latestLinks.links.length; step ++) {
+            t.deepEqual(latestLinks.links[step].data.target, `target-${step}`)
+        }
+
         t.pass()
     })
 }
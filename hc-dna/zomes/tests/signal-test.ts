@@ -56,4 +56,113 @@ orchestrator.registerScenario("basic link signal testing", async (s, t) => {
     t.deepEqual(bobSignalCount, 1);
 })
 
+orchestrator.registerScenario("active agents", async (s, t) => {
+    const [alice, bob] = await s.players([localConductorConfig, localConductorConfig])
+    const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+    const [[bob_sc_happ]] = await bob.installAgentsHapps(installation)
+    await s.shareAllNodes([alice, bob])
+
+    await alice_sc_happ.cells[0].call("social_context", "add_active_agent_link", null)
+    await bob_sc_happ.cells[0].call("social_context", "add_active_agent_link", null)
+    await sleep(2000)
+
+    //Both agents are listed as active, each once
+    const activeAgents = await bob_sc_happ.cells[0].call("social_context", "get_active_agents", null)
+    t.deepEqual(activeAgents.length, 2);
+    t.ok(activeAgents.find((agent) => agent.agent.equals(alice_sc_happ.agent)));
+    t.ok(activeAgents.find((agent) => agent.agent.equals(bob_sc_happ.agent)));
+})
+
+orchestrator.registerScenario("removing the active agent link", async (s, t) => {
+    const [alice, bob] = await s.players([localConductorConfig, localConductorConfig])
+    const bobSignals = [];
+    bob.setSignalHandler((signal) => {
+        bobSignals.push(signal.data.payload.signal);
+    });
+    const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+    const [[bob_sc_happ]] = await bob.installAgentsHapps(installation)
+    await s.shareAllNodes([alice, bob])
+
+    await alice_sc_happ.cells[0].call("social_context", "add_active_agent_link", null)
+    await bob_sc_happ.cells[0].call("social_context", "add_active_agent_link", null)
+    await sleep(2000)
+    bobSignals.length = 0;
+
+    //Going offline removes the agent from the active list and tells the other active agents
+    await alice_sc_happ.cells[0].call("social_context", "remove_active_agent_link", null)
+    await sleep(2000)
+    const activeAgents = await bob_sc_happ.cells[0].call("social_context", "get_active_agents", null)
+    t.deepEqual(activeAgents.length, 1);
+    t.ok(activeAgents[0].agent.equals(bob_sc_happ.agent));
+    const presence = bobSignals.filter((signal) => signal.type == "PresenceChanged");
+    t.deepEqual(presence.length, 1);
+    t.deepEqual(presence[0].payload.online, false);
+})
+
+orchestrator.registerScenario("presence status", async (s, t) => {
+    const [alice, bob] = await s.players([localConductorConfig, localConductorConfig])
+    const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+    const [[bob_sc_happ]] = await bob.installAgentsHapps(installation)
+    await s.shareAllNodes([alice, bob])
+
+    await alice_sc_happ.cells[0].call("social_context", "add_active_agent_link", {status: {availability: {type: "Busy"}, message: "writing", perspective: null}})
+    await bob_sc_happ.cells[0].call("social_context", "add_active_agent_link", null)
+    await sleep(2000)
+
+    //Alice's status is published with her presence, bob has none
+    const activeAgents = await bob_sc_happ.cells[0].call("social_context", "get_active_agents", null)
+    const aliceActive = activeAgents.find((agent) => agent.agent.equals(alice_sc_happ.agent));
+    t.deepEqual(aliceActive.status.message, "writing");
+    const bobActive = activeAgents.find((agent) => agent.agent.equals(bob_sc_happ.agent));
+    t.deepEqual(bobActive.status, null);
+})
+
+orchestrator.registerScenario("ephemeral signals", async (s, t) => {
+    const [alice, bob] = await s.players([localConductorConfig, localConductorConfig])
+    const bobSignals = [];
+    bob.setSignalHandler((signal) => {
+        bobSignals.push(signal.data.payload.signal);
+    });
+    const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+    const [[bob_sc_happ]] = await bob.installAgentsHapps(installation)
+    await s.shareAllNodes([alice, bob])
+
+    await alice_sc_happ.cells[0].call("social_context", "add_active_agent_link", null)
+    await bob_sc_happ.cells[0].call("social_context", "add_active_agent_link", null)
+    await sleep(2000)
+    bobSignals.length = 0;
+
+    //Ephemeral signals reach every active agent without committing anything
+    await alice_sc_happ.cells[0].call("social_context", "send_ephemeral_signal", {kind: "typing", payload: "{}"})
+    await sleep(2000)
+    const ephemeral = bobSignals.filter((signal) => signal.type == "Ephemeral");
+    t.deepEqual(ephemeral.length, 1);
+    t.deepEqual(ephemeral[0].payload.kind, "typing");
+})
+
+orchestrator.registerScenario("direct signals", async (s, t) => {
+    const [alice, bob] = await s.players([localConductorConfig, localConductorConfig])
+    const aliceSignals = [];
+    const bobSignals = [];
+    alice.setSignalHandler((signal) => {
+        aliceSignals.push(signal.data.payload.signal);
+    });
+    bob.setSignalHandler((signal) => {
+        bobSignals.push(signal.data.payload.signal);
+    });
+    const [[alice_sc_happ]] = await alice.installAgentsHapps(installation)
+    const [[bob_sc_happ]] = await bob.installAgentsHapps(installation)
+    await s.shareAllNodes([alice, bob])
+    await sleep(2000)
+
+    //Direct signals only reach the given agents
+    const recipients = await alice_sc_happ.cells[0].call("social_context", "send_direct_signal", {agents: [bob_sc_happ.agent], kind: "ping", payload: "{}"})
+    t.deepEqual(recipients.length, 1);
+    await sleep(2000)
+    const direct = bobSignals.filter((signal) => signal.type == "Direct");
+    t.deepEqual(direct.length, 1);
+    t.deepEqual(direct[0].payload.kind, "ping");
+    t.deepEqual(aliceSignals.filter((signal) => signal.type == "Direct").length, 0);
+})
+
 orchestrator.run()
\ No newline at end of file
@@ -22,11 +22,23 @@ export default async function create(context: LanguageContext): Promise<Language
     (signal) => { linksAdapter.handleHolochainSignal(signal) }
   );
 
-  await linksAdapter.addActiveAgentLink(Holochain);
-  setInterval(
-    await linksAdapter.addActiveAgentLink.bind(Holochain),
-    activeAgentDurationSecs * 1000
-  );
+  //Mark this agent as online and heartbeat again when the reference keeping them online expires
+  //A failed heartbeat is retried after the full duration so presence is never dropped for good
+  //The adapter resends the last published status & subscriptions, so a heartbeat does not reset them
+  const heartbeat = async () => {
+    let nextHeartbeat = activeAgentDurationSecs * 1000;
+    try {
+      const { existing } = await linksAdapter.addActiveAgentLink(Holochain);
+      if (existing) {
+        nextHeartbeat = new Date(existing).getTime() + activeAgentDurationSecs * 1000 - Date.now();
+      }
+    } catch (e) {
+      console.error("Social Context: Failed to mark agent as active", e);
+    } finally {
+      setTimeout(heartbeat, Math.max(nextHeartbeat, 0));
+    }
+  };
+  await heartbeat();
 
   return {
     name,
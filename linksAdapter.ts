@@ -5,6 +5,9 @@ import { DNA_NICK } from "./dna";
 export class JuntoSocialContextLinkAdapter implements LinksAdapter {
   socialContextDna: HolochainLanguageDelegate;
   linkCallback?: NewLinksObserver
+  //Last published presence status & link signal subscriptions, sent again with every heartbeat so they are kept until changed
+  presenceStatus: object | null = null;
+  subscriptions: object[] = [];
 
   constructor(context: LanguageContext) {
     //@ts-ignore
@@ -23,25 +26,43 @@ export class JuntoSocialContextLinkAdapter implements LinksAdapter {
     return []
   }
 
-  async addActiveAgentLink(hcDna: HolochainLanguageDelegate): Promise<any> {
+  //Pass a status to publish it, or null to clear it; when left out the last published status is kept
+  //Likewise pass subscriptions to replace them, or an empty list to receive all link signals
+  async addActiveAgentLink(hcDna: HolochainLanguageDelegate, status?: object | null, subscriptions?: object[]): Promise<any> {
+    if (status !== undefined) {
+      this.presenceStatus = status;
+    }
+    if (subscriptions !== undefined) {
+      this.subscriptions = subscriptions;
+    }
+    const input = { status: this.presenceStatus, subscriptions: this.subscriptions };
     if (hcDna == undefined) {
       //@ts-ignore
       return await this.call(
         DNA_NICK,
         "social_context",
         "add_active_agent_link",
-        null
+        input
       );
     } else {
       return await hcDna.call(
         DNA_NICK,
         "social_context",
         "add_active_agent_link",
-        null
+        input
       );
     }
   }
 
+  async removeActiveAgentLink(): Promise<void> {
+    await this.socialContextDna.call(
+      DNA_NICK,
+      "social_context",
+      "remove_active_agent_link",
+      null
+    );
+  }
+
   async addLink(link: Expression): Promise<void> {
     const data = prepareExpressionLink(link);
     const input = {
@@ -109,7 +130,7 @@ export class JuntoSocialContextLinkAdapter implements LinksAdapter {
     );
     //console.debug("Holchain Social Context: Got Links", links);
 
-    return links;
+    return links.links;
   }
 
   addCallback(callback: NewLinksObserver): number {
@@ -119,7 +140,19 @@ export class JuntoSocialContextLinkAdapter implements LinksAdapter {
 
   handleHolochainSignal(signal: any): void {
     if (this.linkCallback) {
-      this.linkCallback([signal.data.payload], []);
+      //Signal kinds this adapter does not know about (including "Unknown") are ignored
+      const { type, payload } = signal.data.payload.signal;
+      switch (type) {
+        case "Added":
+          this.linkCallback(payload, []);
+          break;
+        case "Removed":
+          this.linkCallback([], payload);
+          break;
+        case "Updated":
+          this.linkCallback([payload.new], [payload.old]);
+          break;
+      }
     }
   }
 }